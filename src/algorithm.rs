@@ -0,0 +1,105 @@
+use crate::error::{Error, Result};
+use crate::internal::dag::config::{DagConfig, MAX_EPOCH};
+use crate::internal::dag::SeedType;
+use crate::{etchash, ethash, evrprogpow, kawpow, meowpow, ubqhash};
+use std::path::PathBuf;
+
+/// Names accepted by `new_algorithm`.
+pub const ALGORITHMS: [&str; 7] = [
+    "ethash",
+    "etchash",
+    "etchash-mordor",
+    "ubqhash",
+    "kawpow",
+    "evrprogpow",
+    "meowpow",
+];
+
+/// Outcome of a share verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    Valid,
+    /// the recomputed mix differs from the claimed one
+    MixMismatch,
+    /// the final hash is above the target
+    AboveTarget,
+}
+
+impl Verification {
+    /// Check a computed (mix, digest) against the claimed mix and a 256-bit big endian target.
+    pub fn check(mix: &[u8], digest: &[u8], claimed_mix: &[u8], target: &[u8; 32]) -> Self {
+        if mix != claimed_mix {
+            Verification::MixMismatch
+        } else if digest > target.as_slice() {
+            Verification::AboveTarget
+        } else {
+            Verification::Valid
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        *self == Verification::Valid
+    }
+}
+
+/// A DAG based proof of work, the epoch, seed and sizes follow the DAG config in use at a height.
+pub trait PowAlgorithm: Send + Sync {
+    /// DAG config in use at the height
+    fn dag_config(&self, height: u64) -> &DagConfig;
+
+    // return mix, digest
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Recompute the share, the mix must match and the big endian digest must not exceed the target.
+    fn verify(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+        mix_hash: &[u8],
+        target: &[u8; 32],
+    ) -> Result<Verification> {
+        let (mix, digest) = self.compute(hash, height, nonce)?;
+        Ok(Verification::check(&mix, &digest, mix_hash, target))
+    }
+
+    fn epoch(&self, height: u64) -> usize {
+        self.dag_config(height).calc_epoch(height as usize)
+    }
+
+    fn seed_hash(&self, height: u64) -> Result<SeedType> {
+        self.dag_config(height).seed_for_epoch(self.epoch(height))
+    }
+
+    fn cache_size(&self, height: u64) -> Result<usize> {
+        let epoch = checked_epoch(self.epoch(height))?;
+        Ok(self.dag_config(height).cache_size(epoch))
+    }
+
+    fn dataset_size(&self, height: u64) -> Result<usize> {
+        let epoch = checked_epoch(self.epoch(height))?;
+        Ok(self.dag_config(height).dataset_size(epoch))
+    }
+}
+
+pub(crate) fn checked_epoch(epoch: usize) -> Result<usize> {
+    if epoch > MAX_EPOCH {
+        return Err(Error::EpochOutOfRange(epoch));
+    }
+    Ok(epoch)
+}
+
+/// Create the mainnet client of the algorithm `name`, see `ALGORITHMS`.
+pub fn new_algorithm(name: &str, storage_dir: PathBuf) -> Result<Box<dyn PowAlgorithm>> {
+    let algorithm: Box<dyn PowAlgorithm> = match name.to_lowercase().as_str() {
+        "ethash" => Box::new(ethash::client::Client::new_ethereum(storage_dir)),
+        "etchash" => Box::new(etchash::client::Client::new_ethereum_classic(storage_dir)),
+        "etchash-mordor" => Box::new(etchash::client::Client::new_mordor(storage_dir)),
+        "ubqhash" => Box::new(ubqhash::client::Client::new_ubiq(storage_dir)),
+        "kawpow" => Box::new(kawpow::client::Client::new_raven_coin(storage_dir)),
+        "evrprogpow" => Box::new(evrprogpow::client::Client::new_evrmore(storage_dir)),
+        "meowpow" => Box::new(meowpow::client::Client::new_meowcoin(storage_dir)),
+        _ => return Err(Error::UnknownAlgorithm(name.to_string())),
+    };
+    Ok(algorithm)
+}
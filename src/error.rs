@@ -0,0 +1,70 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// reading or writing the cache files failed
+    Io(Arc<io::Error>),
+    /// a cache file exists but cannot be used, it is regenerated on the next attempt
+    CorruptCache {
+        path: PathBuf,
+        reason: String,
+    },
+    /// the DAG or algorithm parameters cannot work
+    InvalidConfig(String),
+    /// the epoch is beyond `MAX_EPOCH`
+    EpochOutOfRange(usize),
+    /// the algorithm needs the L1 cache but the config disables it
+    L1Missing,
+    /// compact bits that are negative or overflow 256 bits
+    InvalidBits(u32),
+    /// malformed header, share or encoding
+    InvalidInput(String),
+    UnknownAlgorithm(String),
+    /// the computation of an item of a batch panicked, with the panic message
+    Panicked(String),
+}
+
+impl Error {
+    /// Storage problems that may go away when retried, anything else is a bad input or config.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::Io(_) | Error::CorruptCache { .. })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::CorruptCache { path, reason } => {
+                write!(f, "corrupt cache file {}: {}", path.display(), reason)
+            }
+            Error::InvalidConfig(reason) => write!(f, "invalid config: {}", reason),
+            Error::EpochOutOfRange(epoch) => write!(f, "epoch {} out of range", epoch),
+            Error::L1Missing => write!(f, "l1 cache is not enabled"),
+            Error::InvalidBits(bits) => write!(f, "invalid bits: {:#010x}", bits),
+            Error::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            Error::UnknownAlgorithm(name) => write!(f, "unknown algorithm: {}", name),
+            Error::Panicked(reason) => write!(f, "panicked: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(Arc::new(e))
+    }
+}
@@ -0,0 +1,127 @@
+use crate::algorithm::{PowAlgorithm, Verification};
+use crate::error::Result;
+use crate::ethash;
+use crate::ethash::header::Header;
+use crate::internal::dag::config::{CacheHasher, DagConfig, LookupTable};
+use crate::internal::dag::lookup::{CACHE_SIZES, DATASET_SIZES};
+use std::path::PathBuf;
+
+/// ECIP-1099 activation height on Ethereum Classic mainnet.
+pub const ECIP1099_MAINNET_HEIGHT: u64 = 11_700_000;
+/// ECIP-1099 activation height on the Mordor testnet.
+pub const ECIP1099_MORDOR_HEIGHT: u64 = 2_520_000;
+
+/// Etchash is Ethash with the epoch length doubled from the ECIP-1099 activation height,
+/// the seed keeps using the original epoch length, so the epoch `n` after the activation
+/// uses the seed of the legacy epoch `2n`.
+pub struct Client {
+    activation_height: u64,
+    /// before the activation
+    legacy: ethash::client::Client,
+    /// from the activation on
+    etchash: ethash::client::Client,
+}
+
+impl Client {
+    pub fn new(cfg: DagConfig, activation_height: u64) -> Self {
+        let mut etchash_cfg = cfg.clone();
+        etchash_cfg.name = format!("{}-ECIP1099", cfg.name);
+        etchash_cfg.epoch_length = cfg.epoch_length * 2;
+        Self {
+            activation_height,
+            legacy: ethash::client::Client::new(cfg),
+            etchash: ethash::client::Client::new(etchash_cfg),
+        }
+    }
+
+    pub fn new_ethereum_classic(storage_dir: PathBuf) -> Self {
+        Self::new(
+            Self::dag_config("ETC", storage_dir),
+            ECIP1099_MAINNET_HEIGHT,
+        )
+    }
+
+    pub fn new_mordor(storage_dir: PathBuf) -> Self {
+        Self::new(
+            Self::dag_config("MORDOR", storage_dir),
+            ECIP1099_MORDOR_HEIGHT,
+        )
+    }
+
+    fn dag_config(name: &str, storage_dir: PathBuf) -> DagConfig {
+        DagConfig {
+            name: name.into(),
+            revision: 23,
+            storage_dir,
+
+            dataset_init_bytes: 1 << 30,
+            dataset_growth_bytes: 1 << 23,
+            cache_init_bytes: 1 << 24,
+            cache_growth_bytes: 1 << 17,
+
+            cache_sizes: LookupTable::new(CACHE_SIZES.to_vec()),
+            dataset_sizes: LookupTable::new(DATASET_SIZES.to_vec()),
+
+            mix_bytes: 128,
+            dataset_parents: 256,
+            epoch_length: 30000,
+            seed_epoch_length: 30000,
+
+            cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
+            caches_count: 3,
+            caches_on_disk: 3,
+            caches_lock_mmap: false,
+
+            l1_enabled: false,
+            l1_cache_size: 0,
+            l1_cache_num_items: 0,
+        }
+    }
+
+    fn client(&self, height: u64) -> &ethash::client::Client {
+        if height >= self.activation_height {
+            &self.etchash
+        } else {
+            &self.legacy
+        }
+    }
+
+    pub fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    pub fn epoch_length(&self, height: u64) -> usize {
+        self.client(height).config().epoch_length
+    }
+
+    // return mix, digest
+    pub fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.client(height).compute(hash, height, nonce)
+    }
+
+    /// Verify the seal of a header against the boundary of its difficulty.
+    pub fn verify_header(&self, header: &Header) -> Result<Verification> {
+        self.client(header.number).verify_header(header)
+    }
+
+    // return mix, digest, computed from the full dataset
+    pub fn compute_full(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.client(height).compute_full(hash, height, nonce)
+    }
+}
+
+impl PowAlgorithm for Client {
+    fn dag_config(&self, height: u64) -> &DagConfig {
+        self.client(height).config()
+    }
+
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        Client::compute(self, hash, height, nonce)
+    }
+}
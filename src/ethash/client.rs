@@ -0,0 +1,118 @@
+use crate::algorithm::{PowAlgorithm, Verification};
+use crate::error::{Error, Result};
+use crate::ethash::header::Header;
+use crate::internal::dag::config::{CacheHasher, DagConfig, LookupTable};
+use crate::internal::dag::lookup::{CACHE_SIZES, DATASET_SIZES};
+use crate::internal::dag::{Dag, SeedType};
+use crate::internal::ethash::{hashimoto_full, hashimoto_light};
+use crate::target::ethash_boundary;
+use std::path::PathBuf;
+
+pub struct Client {
+    dag: Dag,
+}
+
+impl Client {
+    pub fn new(cfg: DagConfig) -> Self {
+        let dag = Dag::new(cfg);
+        Self { dag }
+    }
+
+    pub fn new_ethereum(storage_dir: PathBuf) -> Self {
+        let cfg = DagConfig {
+            name: "ETH".into(),
+            revision: 23,
+            storage_dir,
+
+            dataset_init_bytes: 1 << 30,
+            dataset_growth_bytes: 1 << 23,
+            cache_init_bytes: 1 << 24,
+            cache_growth_bytes: 1 << 17,
+
+            cache_sizes: LookupTable::new(CACHE_SIZES.to_vec()),
+            dataset_sizes: LookupTable::new(DATASET_SIZES.to_vec()),
+
+            mix_bytes: 128,
+            dataset_parents: 256,
+            epoch_length: 30000,
+            seed_epoch_length: 30000,
+
+            cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
+            caches_count: 3,
+            caches_on_disk: 3,
+            caches_lock_mmap: false,
+
+            l1_enabled: false,
+            l1_cache_size: 0,
+            l1_cache_num_items: 0,
+        };
+        Self::new(cfg)
+    }
+
+    pub fn config(&self) -> &DagConfig {
+        &self.dag.config
+    }
+
+    pub fn epoch(&self, height: u64) -> usize {
+        self.dag.config.calc_epoch(height as usize)
+    }
+
+    pub fn seed_hash(&self, height: u64) -> Result<SeedType> {
+        self.dag.config.seed_for_epoch(self.epoch(height))
+    }
+
+    // return mix, digest, the dataset items are calculated from the cache
+    pub fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        let epoch = self.dag.config.calc_epoch(height as usize);
+        let cache = self.dag.get_cache(epoch)?;
+        let dataset_size = self.dag.config.dataset_size(epoch);
+        let r = hashimoto_light(
+            hash,
+            nonce,
+            dataset_size,
+            cache.cache(),
+            self.dag.config.dataset_parents,
+        );
+        Ok(r)
+    }
+
+    // return mix, digest, the full dataset of the epoch is generated or loaded on first use
+    pub fn compute_full(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let epoch = self.dag.config.calc_epoch(height as usize);
+        let dataset = self.dag.get_dataset(epoch)?;
+        let r = hashimoto_full(hash, nonce, dataset.data());
+        Ok(r)
+    }
+
+    /// Verify the Ethash seal of a header against the boundary of its difficulty.
+    pub fn verify_header(&self, header: &Header) -> Result<Verification> {
+        // every hash would meet the boundary of a zero difficulty
+        if header.difficulty.is_zero() {
+            return Err(Error::InvalidInput("zero difficulty".into()));
+        }
+        let boundary = ethash_boundary(&header.difficulty);
+        let (mix, digest) = self.compute(&header.seal_hash(), header.number, header.nonce)?;
+        Ok(Verification::check(
+            &mix,
+            &digest,
+            &header.mix_digest,
+            &boundary.to_be_bytes(),
+        ))
+    }
+}
+
+impl PowAlgorithm for Client {
+    fn dag_config(&self, _height: u64) -> &DagConfig {
+        self.config()
+    }
+
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        Client::compute(self, hash, height, nonce)
+    }
+}
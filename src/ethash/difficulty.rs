@@ -0,0 +1,139 @@
+use crate::ethash::header::Header;
+use crate::target::U256;
+
+/// Keccak256 of the RLP encoded empty uncle list.
+pub const EMPTY_UNCLE_HASH: [u8; 32] = [
+    0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a,
+    0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+];
+
+pub const MINIMUM_DIFFICULTY: u64 = 131072;
+const DIFFICULTY_BOUND_DIVISOR: u64 = 2048;
+const EXP_DIFF_PERIOD: u64 = 100000;
+
+/// Activation blocks of the rules changing the difficulty calculation, `None` when the
+/// chain never activated the rule.
+#[derive(Debug, Clone, Default)]
+pub struct ForkSchedule {
+    /// EIP-2, the adjustment is proportional to the block time
+    pub homestead: Option<u64>,
+    /// EIP-100, uncles are taken into account
+    pub eip100: Option<u64>,
+    /// (activation block, bomb delay) pairs sorted by block, e.g. EIP-649 for Byzantium
+    pub bomb_delays: Vec<(u64, u64)>,
+    /// ECIP-1010, (pause block, continue block) of the bomb
+    pub bomb_pause: Option<(u64, u64)>,
+    /// ECIP-1041, the bomb is removed
+    pub bomb_defuse: Option<u64>,
+}
+
+impl ForkSchedule {
+    pub fn ethereum() -> Self {
+        Self {
+            homestead: Some(1_150_000),
+            eip100: Some(4_370_000),
+            bomb_delays: vec![
+                // Byzantium
+                (4_370_000, 3_000_000),
+                // Constantinople
+                (7_280_000, 5_000_000),
+                // Muir Glacier
+                (9_200_000, 9_000_000),
+                // London
+                (12_965_000, 9_700_000),
+                // Arrow Glacier
+                (13_773_000, 10_700_000),
+                // Gray Glacier
+                (15_050_000, 11_400_000),
+            ],
+            bomb_pause: None,
+            bomb_defuse: None,
+        }
+    }
+
+    pub fn ethereum_classic() -> Self {
+        Self {
+            homestead: Some(1_150_000),
+            // Atlantis
+            eip100: Some(8_772_000),
+            bomb_delays: vec![],
+            bomb_pause: Some((3_000_000, 5_000_000)),
+            bomb_defuse: Some(5_900_000),
+        }
+    }
+
+    fn is_active(fork: Option<u64>, number: u64) -> bool {
+        fork.is_some_and(|block| number >= block)
+    }
+
+    fn bomb_delay(&self, number: u64) -> u64 {
+        self.bomb_delays
+            .iter()
+            .rev()
+            .find(|(block, _)| number >= *block)
+            .map_or(0, |(_, delay)| *delay)
+    }
+
+    /// Difficulty bomb of the block `number`.
+    fn bomb(&self, number: u64) -> U256 {
+        if Self::is_active(self.bomb_defuse, number) {
+            return U256::ZERO;
+        }
+        let mut period_ref = number.saturating_sub(self.bomb_delay(number));
+        if let Some((pause, resume)) = self.bomb_pause {
+            if number >= pause {
+                period_ref = if number < resume {
+                    pause
+                } else {
+                    period_ref - (resume - pause)
+                };
+            }
+        }
+        let period = period_ref / EXP_DIFF_PERIOD;
+        if period > 1 {
+            // shifts past 256 bits wrap to zero like the other U256 arithmetic here
+            u32::try_from(period - 2).map_or(U256::ZERO, |shift| U256::ONE << shift)
+        } else {
+            U256::ZERO
+        }
+    }
+}
+
+/// Difficulty of the child of `parent` mined at `time`.
+pub fn calc_difficulty(schedule: &ForkSchedule, time: u64, parent: &Header) -> U256 {
+    let number = parent.number.saturating_add(1);
+    let elapsed = time.saturating_sub(parent.time) as i64;
+    let adjustment = if ForkSchedule::is_active(schedule.eip100, number) {
+        let uncles = if parent.uncle_hash == EMPTY_UNCLE_HASH {
+            1
+        } else {
+            2
+        };
+        (uncles - elapsed / 9).max(-99)
+    } else if ForkSchedule::is_active(schedule.homestead, number) {
+        (1 - elapsed / 10).max(-99)
+    } else if elapsed < 13 {
+        1
+    } else {
+        -1
+    };
+
+    let (step, _) = parent
+        .difficulty
+        .checked_div_rem(&U256::from(DIFFICULTY_BOUND_DIVISOR))
+        .unwrap();
+    let (step, _) = step.overflowing_mul_u64(adjustment.unsigned_abs());
+    let difficulty = if adjustment >= 0 {
+        parent.difficulty.overflowing_add(&step).0
+    } else {
+        parent.difficulty.wrapping_sub(&step)
+    };
+    let difficulty = difficulty.max(U256::from(MINIMUM_DIFFICULTY));
+    difficulty.overflowing_add(&schedule.bomb(number)).0
+}
+
+/// Check that the difficulty of `header` follows from its parent.
+pub fn verify_difficulty(schedule: &ForkSchedule, parent: &Header, header: &Header) -> bool {
+    parent.number.checked_add(1) == Some(header.number)
+        && header.difficulty == calc_difficulty(schedule, header.time, parent)
+}
@@ -0,0 +1,135 @@
+use crate::error::{Error, Result};
+use crate::internal::crypto::keccak::keccak256;
+use crate::internal::rlp::{self, Rlp};
+use crate::target::U256;
+
+/// Number of fields of a header before London.
+const LEGACY_FIELDS: usize = 15;
+
+/// Ethereum / Ethereum Classic block header.
+///
+/// The fork dependent fields are appended to the RLP list in declaration order when set:
+/// `base_fee` since London, `withdrawals_root` since Shanghai, and the blob fields since Cancun.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub parent_hash: [u8; 32],
+    pub uncle_hash: [u8; 32],
+    pub coinbase: [u8; 20],
+    pub state_root: [u8; 32],
+    pub tx_root: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub bloom: Vec<u8>,
+    pub difficulty: U256,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub time: u64,
+    pub extra: Vec<u8>,
+    pub mix_digest: [u8; 32],
+    pub nonce: u64,
+
+    pub base_fee: Option<U256>,
+    pub withdrawals_root: Option<[u8; 32]>,
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+    pub parent_beacon_root: Option<[u8; 32]>,
+}
+
+impl Header {
+    pub fn from_rlp(b: &[u8]) -> Result<Self> {
+        let item = rlp::decode(b)?;
+        let fields = item.as_list()?;
+        let optional = |i: usize| fields.get(LEGACY_FIELDS + i);
+        if !matches!(fields.len(), 15 | 16 | 17 | 20) {
+            return Err(Error::InvalidInput(format!(
+                "invalid header field count: {}",
+                fields.len()
+            )));
+        }
+        let nonce: [u8; 8] = fields[14].as_fixed()?;
+        Ok(Self {
+            parent_hash: fields[0].as_fixed()?,
+            uncle_hash: fields[1].as_fixed()?,
+            coinbase: fields[2].as_fixed()?,
+            state_root: fields[3].as_fixed()?,
+            tx_root: fields[4].as_fixed()?,
+            receipt_root: fields[5].as_fixed()?,
+            bloom: fields[6].as_fixed::<256>()?.to_vec(),
+            difficulty: decode_u256(&fields[7])?,
+            number: fields[8].as_u64()?,
+            gas_limit: fields[9].as_u64()?,
+            gas_used: fields[10].as_u64()?,
+            time: fields[11].as_u64()?,
+            extra: fields[12].as_bytes()?.to_vec(),
+            mix_digest: fields[13].as_fixed()?,
+            nonce: u64::from_be_bytes(nonce),
+            base_fee: optional(0).map(decode_u256).transpose()?,
+            withdrawals_root: optional(1).map(|f| f.as_fixed()).transpose()?,
+            blob_gas_used: optional(2).map(|f| f.as_u64()).transpose()?,
+            excess_blob_gas: optional(3).map(|f| f.as_u64()).transpose()?,
+            parent_beacon_root: optional(4).map(|f| f.as_fixed()).transpose()?,
+        })
+    }
+
+    pub fn rlp(&self) -> Vec<u8> {
+        self.encode(true)
+    }
+
+    /// Block hash, keccak256 of the RLP encoded header.
+    pub fn hash(&self) -> [u8; 32] {
+        to_hash(keccak256(&self.rlp()))
+    }
+
+    /// The hash sealed by Ethash, keccak256 of the RLP encoded header without mix digest and
+    /// nonce.
+    pub fn seal_hash(&self) -> [u8; 32] {
+        to_hash(keccak256(&self.encode(false)))
+    }
+
+    fn encode(&self, with_seal: bool) -> Vec<u8> {
+        let mut out = Vec::with_capacity(600);
+        rlp::encode_bytes(&self.parent_hash, &mut out);
+        rlp::encode_bytes(&self.uncle_hash, &mut out);
+        rlp::encode_bytes(&self.coinbase, &mut out);
+        rlp::encode_bytes(&self.state_root, &mut out);
+        rlp::encode_bytes(&self.tx_root, &mut out);
+        rlp::encode_bytes(&self.receipt_root, &mut out);
+        rlp::encode_bytes(&self.bloom, &mut out);
+        rlp::encode_uint(&self.difficulty.to_be_bytes(), &mut out);
+        rlp::encode_u64(self.number, &mut out);
+        rlp::encode_u64(self.gas_limit, &mut out);
+        rlp::encode_u64(self.gas_used, &mut out);
+        rlp::encode_u64(self.time, &mut out);
+        rlp::encode_bytes(&self.extra, &mut out);
+        if with_seal {
+            rlp::encode_bytes(&self.mix_digest, &mut out);
+            rlp::encode_bytes(&self.nonce.to_be_bytes(), &mut out);
+        }
+        if let Some(base_fee) = &self.base_fee {
+            rlp::encode_uint(&base_fee.to_be_bytes(), &mut out);
+        }
+        if let Some(withdrawals_root) = &self.withdrawals_root {
+            rlp::encode_bytes(withdrawals_root, &mut out);
+        }
+        if let Some(blob_gas_used) = self.blob_gas_used {
+            rlp::encode_u64(blob_gas_used, &mut out);
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            rlp::encode_u64(excess_blob_gas, &mut out);
+        }
+        if let Some(parent_beacon_root) = &self.parent_beacon_root {
+            rlp::encode_bytes(parent_beacon_root, &mut out);
+        }
+        rlp::encode_list(&out)
+    }
+}
+
+fn decode_u256(item: &Rlp) -> Result<U256> {
+    Ok(U256::from_be_slice(item.as_uint(32)?).unwrap())
+}
+
+fn to_hash(b: Vec<u8>) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&b);
+    hash
+}
@@ -0,0 +1,3 @@
+pub mod client;
+pub mod difficulty;
+pub mod header;
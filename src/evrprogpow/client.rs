@@ -0,0 +1,12 @@
+use crate::internal::progpow;
+use crate::internal::progpow::variant::Variant;
+use std::path::PathBuf;
+
+/// EvrProgPoW is the ProgPoW client of the Evrmore variant.
+pub type Client = progpow::client::Client;
+
+impl Client {
+    pub fn new_evrmore(storage_dir: PathBuf) -> Self {
+        Self::new(Variant::evrmore(storage_dir))
+    }
+}
@@ -1,4 +1,7 @@
-use sha3::{Digest, Keccak256, Keccak512};
+use sha3::{Digest, Keccak256, Keccak512, Sha3_512};
+
+/// Hash `input` into `output` starting at `from_index`.
+pub type FillFn = fn(&[u8], &mut [u8], usize);
 
 pub fn fill_sha256(input: &[u8], a: &mut [u8], from_index: usize) {
     let mut hasher = Keccak256::default();
@@ -17,3 +20,26 @@ pub fn fill_sha512(input: &[u8], output: &mut [u8], from_index: usize) {
         output[from_index + i] = out[i];
     }
 }
+
+pub fn fill_sha3_512(input: &[u8], output: &mut [u8], from_index: usize) {
+    let mut hasher = Sha3_512::default();
+    hasher.update(input);
+    let out = hasher.finalize();
+    for i in 0..out.len() {
+        output[from_index + i] = out[i];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fill_sha3_512() {
+        let mut output = [0u8; 64];
+        fill_sha3_512(b"abc", &mut output, 0);
+        assert_eq!(hex::encode(output), "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0");
+        fill_sha512(b"abc", &mut output, 0);
+        assert_ne!(hex::encode(output), "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0");
+    }
+}
@@ -21,7 +21,7 @@ pub const RCK8: [u32; 22] = [
 ];
 
 pub fn rol(x: u32, s: usize) -> u32 {
-    x << s | x >> (32 - s)
+    x.rotate_left(s as u32)
 }
 
 pub fn keccak_f800(state: &mut [u32; 25]) {
@@ -2,3 +2,4 @@ pub mod fnv;
 pub mod hasher;
 pub mod keccak;
 pub mod prime;
+pub mod sha256;
@@ -106,7 +106,7 @@ pub fn is_prime(n: usize) -> bool {
         (0xFFFF_FFFF_FFFF_FFFF, &[2, 3, 5, 7, 11, 13, 17, 19, 23]),
     ];
 
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         return n == 2;
     }
     if n == 1 {
@@ -115,7 +115,7 @@ pub fn is_prime(n: usize) -> bool {
 
     let mut d = n - 1;
     let mut s = 0;
-    while d % 2 == 0 {
+    while d.is_multiple_of(2) {
         d /= 2;
         s += 1
     }
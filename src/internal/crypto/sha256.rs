@@ -0,0 +1,26 @@
+use sha2::{Digest, Sha256};
+
+pub fn sha256(b: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::default();
+    hasher.update(b);
+    hasher.finalize().into()
+}
+
+/// Bitcoin style double SHA256.
+pub fn sha256d(b: &[u8]) -> [u8; 32] {
+    sha256(&sha256(b))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sha256d() {
+        assert_eq!(
+            hex::encode(sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(sha256d(b"abc"), sha256(&sha256(b"abc")));
+    }
+}
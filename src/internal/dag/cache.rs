@@ -1,24 +1,40 @@
+use crate::error::{Error, Result};
 use crate::internal::crypto::fnv::{fnv1, fnv64};
-use crate::internal::crypto::hasher::fill_sha512;
-use crate::internal::dag::config::{DagConfig, HASH_BYTES, WORD_BYTES};
+use crate::internal::crypto::hasher::{fill_sha512, FillFn};
+use crate::internal::dag::config::{DagConfig, DataKind, HASH_BYTES, WORD_BYTES};
 use crate::internal::dag::data_file::DataFile;
 use crate::internal::dag::SeedType;
-use anyhow::Result;
 use byteorder::{ByteOrder, LittleEndian};
 use std::ops::BitXor;
 use std::thread;
 
 /// Make an Ethash cache using the given seed.
-pub fn generate_cache(cache: &mut [u8], seed: &SeedType, cache_rounds: usize) {
-    let cache_len = cache.len() - 1;
-    assert_eq!(cache_len % HASH_BYTES, 0);
+pub fn generate_cache(cache: &mut [u8], seed: &SeedType, cache_rounds: usize) -> Result<()> {
+    generate_cache_with(cache, seed, cache_rounds, fill_sha512)
+}
+
+/// Make a cache using the given seed and 512 bits hash function, e.g. Ubqhash uses SHA3-512.
+/// `cache` holds whole 64 bytes items followed by the finished flag.
+pub fn generate_cache_with(
+    cache: &mut [u8],
+    seed: &SeedType,
+    cache_rounds: usize,
+    fill: FillFn,
+) -> Result<()> {
+    let cache_len = cache.len().saturating_sub(1);
+    if cache_len == 0 || !cache_len.is_multiple_of(HASH_BYTES) {
+        return Err(Error::InvalidConfig(format!(
+            "cache of {} bytes is not a multiple of {}",
+            cache_len, HASH_BYTES
+        )));
+    }
     let n = cache_len / HASH_BYTES;
 
-    fill_sha512(seed, cache, 0);
+    fill(seed, cache, 0);
 
     for i in 1..n {
         let (last, next) = cache.split_at_mut(i * HASH_BYTES);
-        fill_sha512(&last[(last.len() - 64)..], next, 0);
+        fill(&last[(last.len() - 64)..], next, 0);
     }
 
     for _ in 0..cache_rounds {
@@ -31,10 +47,11 @@ pub fn generate_cache(cache: &mut [u8], seed: &SeedType, cache_rounds: usize) {
                 let b = cache[v * 64 + j];
                 r[j] = a.bitxor(b);
             }
-            fill_sha512(&r, cache, i * 64);
+            fill(&r, cache, i * 64);
         }
     }
     cache[cache_len] = 1;
+    Ok(())
 }
 
 /// Calculate the dataset item.
@@ -117,14 +134,53 @@ pub fn generate_dataset_item_unit(
     data
 }
 
-pub fn generate_l1_cache(l1: &mut [u8], cache: &[u8], dataset_parents: usize) {
-    let size = l1.len() - 1;
+/// The L1 cache is the head of the full dataset.
+pub fn generate_l1_cache(l1: &mut [u8], cache: &[u8], dataset_parents: usize) -> Result<()> {
+    generate_dataset(l1, cache, dataset_parents)
+}
+
+/// Generate the full dataset from the cache on all cores, the last byte is the finished flag.
+pub fn generate_dataset(dataset: &mut [u8], cache: &[u8], dataset_parents: usize) -> Result<()> {
+    if dataset.is_empty() {
+        return Err(Error::InvalidConfig("empty dataset".into()));
+    }
+    let size = dataset.len() - 1;
     let rows = size / HASH_BYTES;
-    for i in 0..rows {
-        let item = generate_dataset_item(cache, i, dataset_parents);
-        l1[i * HASH_BYTES..(i + 1) * HASH_BYTES].copy_from_slice(&item);
+    generate_dataset_rows(&mut dataset[..rows * HASH_BYTES], 0, cache, dataset_parents)?;
+    dataset[size] = 1;
+    Ok(())
+}
+
+/// Generate the dataset items from `first_row` on all cores, `items` holds whole items.
+pub fn generate_dataset_rows(
+    items: &mut [u8],
+    first_row: usize,
+    cache: &[u8],
+    dataset_parents: usize,
+) -> Result<()> {
+    if cache.len() < HASH_BYTES {
+        return Err(Error::InvalidConfig(format!(
+            "dataset from a cache of {} bytes",
+            cache.len()
+        )));
     }
-    l1[size] = 1;
+    let rows = items.len() / HASH_BYTES;
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_rows = rows.div_ceil(threads).max(1);
+    thread::scope(|s| {
+        for (k, chunk) in items[..rows * HASH_BYTES]
+            .chunks_mut(chunk_rows * HASH_BYTES)
+            .enumerate()
+        {
+            s.spawn(move || {
+                for (j, out) in chunk.chunks_mut(HASH_BYTES).enumerate() {
+                    let i = first_row + k * chunk_rows + j;
+                    out.copy_from_slice(&generate_dataset_item(cache, i, dataset_parents));
+                }
+            });
+        }
+    });
+    Ok(())
 }
 
 #[derive(Clone)]
@@ -134,6 +190,10 @@ pub struct Cache {
 }
 
 impl Cache {
+    pub fn epoch(&self) -> usize {
+        self.cache.epoch
+    }
+
     pub fn cache(&self) -> &[u8] {
         self.cache.data()
     }
@@ -147,9 +207,10 @@ impl Cache {
     }
 
     pub fn generate(epoch: usize, config: &DagConfig) -> Result<Self> {
-        let cache_data_file = DataFile::generate(config, epoch, None)?;
+        let cache_data_file = DataFile::generate(config, epoch, DataKind::Cache, None)?;
         let l1_data_file = if config.l1_enabled {
-            let l1_data_file = DataFile::generate(config, epoch, Some(cache_data_file.data()))?;
+            let l1_data_file =
+                DataFile::generate(config, epoch, DataKind::L1, Some(cache_data_file.data()))?;
             Some(l1_data_file)
         } else {
             None
@@ -162,13 +223,16 @@ impl Cache {
     }
 
     fn generate_next(next_epoch: usize, config: DagConfig) {
-        thread::spawn(
-            move || match DataFile::generate(&config, next_epoch, None) {
+        thread::spawn(move || {
+            match DataFile::generate(&config, next_epoch, DataKind::Cache, None) {
                 Ok(cache_data_file) => {
                     if config.l1_enabled {
-                        if let Err(e) =
-                            DataFile::generate(&config, next_epoch, Some(cache_data_file.data()))
-                        {
+                        if let Err(e) = DataFile::generate(
+                            &config,
+                            next_epoch,
+                            DataKind::L1,
+                            Some(cache_data_file.data()),
+                        ) {
                             log::error!("generate next l1 cache failed: {:?}", e);
                         }
                     }
@@ -176,15 +240,15 @@ impl Cache {
                 Err(e) => {
                     log::error!("generate next cache failed: {:?}", e);
                 }
-            },
-        );
+            }
+        });
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::internal::dag::config::LookupTable;
+    use crate::internal::dag::config::{CacheHasher, LookupTable};
     use std::path::PathBuf;
 
     #[test]
@@ -227,7 +291,9 @@ mod test {
             seed_epoch_length: 30000,
 
             cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
             caches_count: 3,
+            caches_on_disk: 3,
             caches_lock_mmap: false,
 
             l1_enabled: false,
@@ -236,8 +302,8 @@ mod test {
         };
 
         let mut g_cache = vec![0; size + 1];
-        let seed = d.seed_hash(epoch * d.epoch_length + 1);
-        generate_cache(&mut g_cache, &seed, d.cache_rounds);
+        let seed = d.seed_hash(epoch * d.epoch_length + 1).unwrap();
+        generate_cache(&mut g_cache, &seed, d.cache_rounds).unwrap();
         let cache_s = hex::encode(&g_cache[0..size]);
         assert_eq!(cache_s, cache_str);
 
@@ -260,9 +326,9 @@ mod test {
             "1e114353e6a31b09340f4078b8a8c6ce6ff4213067a8f21020f78aff4f8b472b701ef730aacb8ce7806ea31b14abe8f8efdd6357ca299d339abc4e43ba324ad1" +
             "efe6eb1a5a6e137daa6ec9f6be30931ca368a944cfcf2a0a29f9a9664188f0466e6f078c347f9fe26a9a89d2029462b19245f24ace47aecace6ef85a4e96b31b" +
             "5f470eb0165c6375eb8f245d50a25d521d1e569e3b2dccce626752bb26eae624a24511e831a81fab6898a791579f462574ca4851e6588116493dbccc3072e0c5";
-        let seed = d.seed_hash(epoch * d.epoch_length + 1);
+        let seed = d.seed_hash(epoch * d.epoch_length + 1).unwrap();
         let mut g_cache = vec![0; size + 1];
-        generate_cache(&mut g_cache, &seed, d.cache_rounds);
+        generate_cache(&mut g_cache, &seed, d.cache_rounds).unwrap();
         let cache_s = hex::encode(&g_cache[0..size]);
         assert_eq!(cache_s, cache_str);
     }
@@ -292,7 +358,9 @@ mod test {
             seed_epoch_length: 7500,
 
             cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
             caches_count: 3,
+            caches_on_disk: 3,
             caches_lock_mmap: false,
 
             l1_enabled: true,
@@ -304,10 +372,10 @@ mod test {
             let index = tt.1;
             let size = d.cache_size(epoch);
             let mut cache = vec![0; size + 1];
-            let seed = d.seed_hash(epoch * d.epoch_length + 1);
-            generate_cache(&mut cache, &seed, d.cache_rounds);
+            let seed = d.seed_hash(epoch * d.epoch_length + 1).unwrap();
+            generate_cache(&mut cache, &seed, d.cache_rounds).unwrap();
             let item = generate_dataset_item(&cache, index, d.dataset_parents);
-            assert_eq!(hex::encode(&item), tt.2);
+            assert_eq!(hex::encode(item), tt.2);
         }
     }
 }
@@ -1,10 +1,15 @@
+use crate::error::{Error, Result};
+use crate::internal::crypto::hasher::{fill_sha3_512, fill_sha512, FillFn};
 use crate::internal::crypto::prime::is_prime;
-use crate::internal::dag::SeedType;
-use sha3::{Digest, Keccak256};
+use crate::internal::dag::{seed, SeedType};
 use std::path::PathBuf;
 
 pub const HASH_BYTES: usize = 64;
 pub const WORD_BYTES: usize = 4;
+/// Last epoch `DagConfig::epoch_for_seed` looks for.
+pub const MAX_SEED_EPOCH: usize = 8192;
+/// Last epoch a cache is generated for, heights beyond it are rejected.
+pub const MAX_EPOCH: usize = 1 << 16;
 
 #[derive(Default, Debug, Clone)]
 pub struct LookupTable {
@@ -17,6 +22,43 @@ impl LookupTable {
     }
 }
 
+/// The 512 bits hash function used to generate the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheHasher {
+    Keccak512,
+    /// Standard SHA3-512, used by Ubqhash
+    Sha3_512,
+}
+
+impl CacheHasher {
+    pub fn fill_fn(&self) -> FillFn {
+        match self {
+            CacheHasher::Keccak512 => fill_sha512,
+            CacheHasher::Sha3_512 => fill_sha3_512,
+        }
+    }
+}
+
+/// Data generated per epoch and stored under `storage_dir`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataKind {
+    Cache,
+    /// the head of the dataset read by ProgPoW
+    L1,
+    /// the full dataset, only generated for mining
+    Dataset,
+}
+
+impl DataKind {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            DataKind::Cache => "cache",
+            DataKind::L1 => "l1",
+            DataKind::Dataset => "full",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DagConfig {
     pub name: String,
@@ -45,8 +87,11 @@ pub struct DagConfig {
 
     // cache variables
     pub cache_rounds: usize,
+    pub cache_hasher: CacheHasher,
     // Maximum number of caches to keep before eviction (only init, don't modify)
     pub caches_count: usize,
+    // Maximum number of epochs whose files are kept in storage_dir, for each kind
+    pub caches_on_disk: usize,
     pub caches_lock_mmap: bool,
 
     // // L1 variables
@@ -56,26 +101,57 @@ pub struct DagConfig {
 }
 
 impl DagConfig {
-    pub fn file_path(&self, epoch: usize, is_l1: bool) -> PathBuf {
-        let name = if is_l1 {
-            format!("l1-{}-{:?}", self.name, epoch)
-        } else {
-            format!("cache-{}-{:?}", self.name, epoch)
-        };
+    pub fn file_path(&self, epoch: usize, kind: DataKind) -> PathBuf {
+        let name = format!("{}-{}-{:?}", kind.prefix(), self.name, epoch);
         self.storage_dir.join(name)
     }
 
-    pub fn seed_hash(&self, height: usize) -> SeedType {
-        let mut seed: [u8; 32] = Default::default();
-        if height < self.seed_epoch_length {
-            return seed;
+    /// Advisory lock held while the file of `file_path` is loaded or generated.
+    pub fn lock_path(&self, epoch: usize, kind: DataKind) -> PathBuf {
+        let mut path = self.file_path(epoch, kind).into_os_string();
+        path.push(".lock");
+        PathBuf::from(path)
+    }
+
+    /// Bytes of the data of the epoch, without the finished flag.
+    pub fn data_size(&self, epoch: usize, kind: DataKind) -> usize {
+        match kind {
+            DataKind::Cache => self.cache_size(epoch),
+            DataKind::L1 => self.l1_cache_size,
+            DataKind::Dataset => self.dataset_size(epoch),
+        }
+    }
+
+    /// Seed of the block at `height`, heights beyond `MAX_EPOCH` are rejected.
+    pub fn seed_hash(&self, height: usize) -> Result<SeedType> {
+        let epoch = self.calc_epoch(height);
+        if epoch > MAX_EPOCH {
+            return Err(Error::EpochOutOfRange(epoch));
         }
-        for _ in 0..height / self.seed_epoch_length {
-            let mut hasher = Keccak256::default();
-            hasher.update(&mut seed);
-            seed = hasher.finalize().to_vec().try_into().unwrap();
+        Ok(seed::seed(height / self.seed_epoch_length))
+    }
+
+    /// Round of the seed chain the epoch uses, None if it overflows.
+    fn seed_round(&self, epoch: usize) -> Option<usize> {
+        let height = epoch.checked_mul(self.epoch_length)?.checked_add(1)?;
+        Some(height / self.seed_epoch_length)
+    }
+
+    /// Seed of the cache of the epoch, epochs beyond `MAX_EPOCH` are rejected.
+    pub fn seed_for_epoch(&self, epoch: usize) -> Result<SeedType> {
+        match self.seed_round(epoch) {
+            Some(round) if epoch <= MAX_EPOCH => Ok(seed::seed(round)),
+            _ => Err(Error::EpochOutOfRange(epoch)),
         }
-        seed
+    }
+
+    /// Epoch using the seed, e.g. the seed hash of a stratum job, the seed table is
+    /// extended up to `MAX_SEED_EPOCH` to find it.
+    pub fn epoch_for_seed(&self, seed: &SeedType) -> Option<usize> {
+        let round = seed::round(seed, self.seed_round(MAX_SEED_EPOCH)?)?;
+        // not every round starts an epoch, e.g. the odd ones after ECIP-1099
+        let epoch = round * self.seed_epoch_length / self.epoch_length;
+        (self.seed_round(epoch) == Some(round)).then_some(epoch)
     }
 
     pub fn dataset_size(&self, epoch: usize) -> usize {
@@ -95,21 +171,105 @@ impl DagConfig {
 
     fn calc_cache_size(&self, epoch: usize) -> usize {
         let mut size = self.cache_init_bytes + self.cache_growth_bytes * epoch - HASH_BYTES;
-        if !is_prime(size / HASH_BYTES) {
-            size -= 2 * self.mix_bytes;
+        while !is_prime(size / HASH_BYTES) {
+            size -= 2 * HASH_BYTES;
         }
         size
     }
 
     fn calc_dataset_size(&self, epoch: usize) -> usize {
         let mut size = self.dataset_init_bytes + self.dataset_growth_bytes * epoch - self.mix_bytes;
-        if !is_prime(size / self.mix_bytes) {
+        while !is_prime(size / self.mix_bytes) {
             size -= 2 * self.mix_bytes;
         }
         size
     }
 
+    /// Check that caches and datasets of every epoch up to `MAX_EPOCH` can be sized.
+    pub fn validate(&self) -> Result<()> {
+        let invalid =
+            |reason: &str| Err(Error::InvalidConfig(format!("{}: {}", self.name, reason)));
+        if self.epoch_length == 0 || self.seed_epoch_length == 0 {
+            return invalid("zero epoch length");
+        }
+        if self.mix_bytes == 0 {
+            return invalid("zero mix bytes");
+        }
+        // the size search steps down by two items until a prime count, it must not pass zero
+        if self.cache_init_bytes < 3 * HASH_BYTES {
+            return invalid("cache init bytes below 3 items");
+        }
+        if self.dataset_init_bytes < 3 * self.mix_bytes {
+            return invalid("dataset init bytes below 3 mixes");
+        }
+        let overflows = |init: usize, growth: usize| {
+            growth
+                .checked_mul(MAX_EPOCH)
+                .and_then(|g| g.checked_add(init))
+                .is_none()
+        };
+        if overflows(1, self.epoch_length) {
+            return invalid("epoch length overflows");
+        }
+        if overflows(self.cache_init_bytes, self.cache_growth_bytes)
+            || overflows(self.dataset_init_bytes, self.dataset_growth_bytes)
+        {
+            return invalid("sizes overflow");
+        }
+        if self.caches_count == 0 || self.caches_on_disk == 0 {
+            return invalid("zero caches count");
+        }
+        if self.l1_enabled
+            && (self.l1_cache_size == 0 || !self.l1_cache_size.is_multiple_of(HASH_BYTES))
+        {
+            return invalid("l1 cache size is not a multiple of 64 bytes");
+        }
+        Ok(())
+    }
+
     pub fn calc_epoch(&self, height: usize) -> usize {
         height / self.epoch_length
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::internal::dag::lookup::{CACHE_SIZES, DATASET_SIZES};
+
+    #[test]
+    fn test_calc_sizes() {
+        let d = DagConfig {
+            name: "ETH".to_string(),
+            revision: 23,
+            storage_dir: PathBuf::from("/tmp"),
+
+            dataset_init_bytes: 1 << 30,
+            dataset_growth_bytes: 1 << 23,
+            cache_init_bytes: 1 << 24,
+            cache_growth_bytes: 1 << 17,
+
+            dataset_sizes: LookupTable::default(),
+            cache_sizes: LookupTable::default(),
+
+            mix_bytes: 128,
+            dataset_parents: 256,
+            epoch_length: 30000,
+            seed_epoch_length: 30000,
+
+            cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
+            caches_count: 3,
+            caches_on_disk: 3,
+            caches_lock_mmap: false,
+
+            l1_enabled: false,
+            l1_cache_size: 0,
+            l1_cache_num_items: 0,
+        };
+        for epoch in [0, 1, 22, 195, 389, 1024, 2047] {
+            assert_eq!(d.cache_size(epoch), CACHE_SIZES[epoch]);
+            assert_eq!(d.dataset_size(epoch), DATASET_SIZES[epoch]);
+        }
+    }
+}
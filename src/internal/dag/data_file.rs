@@ -1,131 +1,558 @@
-use crate::internal::dag::cache::{generate_cache, generate_l1_cache};
-use crate::internal::dag::config::DagConfig;
-use anyhow::Result;
+use crate::error::{Error, Result};
+use crate::internal::crypto::sha256::sha256;
+use crate::internal::dag::cache::{
+    generate_cache_with, generate_dataset_item, generate_dataset_rows, generate_l1_cache,
+};
+use crate::internal::dag::config::{DagConfig, DataKind, HASH_BYTES};
+use crate::internal::dag::file_header::{config_hash, FileHeader, HEADER_SIZE};
+use byteorder::{ByteOrder, LittleEndian};
+use fs2::FileExt;
 use memmap::{Mmap, MmapMut};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
-use std::fs::OpenOptions;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::{fs, io};
+use std::time::SystemTime;
+use std::{fs, io, thread};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Clone)]
 pub struct DataFile {
-    pub is_l1: bool,
     pub epoch: usize,
     mmap: Arc<Mmap>,
+    /// set when the background check of a loaded dataset finds a checksum mismatch
+    corrupt: Arc<AtomicBool>,
 }
 
-static GENERATOR_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+/// Dataset rows generated between two checkpoints, 64 MiB.
+const CHECKPOINT_ROWS: usize = 1 << 20;
+
+// serializes the generation of each file in this process where the lock files don't, e.g.
+// flock emulated with per-process fcntl locks on NFS, only taken once the file lock is held
+static GENERATOR_LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = Lazy::new(Default::default);
 
 impl DataFile {
-    pub fn generate(config: &DagConfig, epoch: usize, cache: Option<&[u8]>) -> Result<Self> {
-        let _lock = GENERATOR_LOCK.lock();
-        if let Ok(cache) = Self::new_from_file(config, epoch, cache.is_some()) {
-            return Ok(cache);
+    /// Load or generate the data of the epoch, the L1 cache and the dataset are
+    /// generated from `cache`. A process generating the file holds its lock file
+    /// exclusively, others wait for it and then map the finished file.
+    pub fn generate(
+        config: &DagConfig,
+        epoch: usize,
+        kind: DataKind,
+        cache: Option<&[u8]>,
+    ) -> Result<Self> {
+        let lock_path = config.lock_path(epoch, kind);
+        let lock_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)?;
+        lock_file.lock_shared()?;
+        if let Ok(data) = Self::new_from_file(config, epoch, kind) {
+            return Ok(data);
+        }
+        // flock can't upgrade atomically, check again once no one else is generating
+        lock_file.unlock()?;
+        lock_file.lock_exclusive()?;
+        let generator_lock = GENERATOR_LOCKS
+            .lock()
+            .entry(lock_path.clone())
+            .or_default()
+            .clone();
+        let result = {
+            let _lock = generator_lock.lock();
+            Self::generate_locked(config, epoch, kind, cache)
+        };
+        // clones are only taken with the map locked, no other thread waits on the lock
+        // when the map and this thread hold the only ones
+        let mut generator_locks = GENERATOR_LOCKS.lock();
+        if Arc::strong_count(&generator_lock) == 2 {
+            generator_locks.remove(&lock_path);
+        }
+        result
+    }
+
+    fn generate_locked(
+        config: &DagConfig,
+        epoch: usize,
+        kind: DataKind,
+        cache: Option<&[u8]>,
+    ) -> Result<Self> {
+        if let Ok(data) = Self::new_from_file(config, epoch, kind) {
+            return Ok(data);
         }
-        Self::new(config, epoch, cache)
+        if let Some(data) = Self::migrate(config, epoch, kind, cache)? {
+            return Ok(data);
+        }
+        Self::new(config, epoch, kind, cache)
     }
 
-    fn new_from_file(config: &DagConfig, epoch: usize, is_l1: bool) -> Result<Self> {
-        let path = config.file_path(epoch, is_l1);
+    fn new_from_file(config: &DagConfig, epoch: usize, kind: DataKind) -> Result<Self> {
+        let path = config.file_path(epoch, kind);
         let file = OpenOptions::new()
             .read(true)
             .write(false)
             .create(false)
             .open(&path)?;
         let mmap = unsafe { Mmap::map(&file)? };
-        let cache_size = if is_l1 {
-            config.l1_cache_size
-        } else {
-            config.cache_size(epoch)
+        let corrupt = |reason: String| {
+            Err(Error::CorruptCache {
+                path: path.clone(),
+                reason,
+            })
+        };
+        let cache_size = config.data_size(epoch, kind);
+        if mmap.len() != HEADER_SIZE + cache_size + 1 {
+            return corrupt(format!(
+                "{} bytes, expected {}",
+                mmap.len(),
+                HEADER_SIZE + cache_size + 1
+            ));
+        }
+        let header = match FileHeader::from_bytes(&mmap) {
+            Some(header) => header,
+            None => return corrupt("missing header".into()),
         };
-        if mmap.len() != cache_size + 1 {
-            bail!("invalid cache file");
+        if let Some(reason) = header.mismatch(config, epoch, kind) {
+            return corrupt(reason);
         }
         // if the last bit is 1, the data is valid
-        let finished_flag = mmap[cache_size];
+        let finished_flag = mmap[mmap.len() - 1];
         if finished_flag != 1 {
-            bail!("invalid cache file, not finished");
+            return corrupt("not finished".into());
         }
-        Ok(Self {
+        // a dataset is GBs, it is served while its checksum is checked in the background
+        if kind != DataKind::Dataset
+            && sha256(&mmap[HEADER_SIZE..mmap.len() - 1]) != header.checksum
+        {
+            return corrupt("checksum mismatch".into());
+        }
+        // mark it used for `prune`, without it the file is pruned as if last used when it
+        // was generated, e.g. when it belongs to another user
+        if let Err(error) = file.set_modified(SystemTime::now()) {
+            warn!("Error marking DAG cache {:?} as used: {:?}", path, error);
+        }
+        let data = Self {
             epoch,
-            is_l1,
             mmap: Arc::new(mmap),
-        })
+            corrupt: Default::default(),
+        };
+        if kind == DataKind::Dataset {
+            data.check_in_background(path, header.checksum);
+        }
+        Ok(data)
     }
 
-    fn new(config: &DagConfig, epoch: usize, cache: Option<&[u8]>) -> Result<Self> {
-        let path = config.file_path(epoch, cache.is_some());
+    /// Compare the data with its checksum without holding up the caller. On a mismatch the
+    /// file is removed and the data marked corrupt, so that it is generated again.
+    fn check_in_background(&self, path: PathBuf, checksum: [u8; 32]) {
+        let mmap = self.mmap.clone();
+        let corrupt = self.corrupt.clone();
+        thread::spawn(move || {
+            if sha256(&mmap[HEADER_SIZE..mmap.len() - 1]) != checksum {
+                warn!("checksum mismatch of DAG cache {:?}, removing it", path);
+                remove_file(&path);
+                corrupt.store(true, Ordering::Release);
+            }
+        });
+    }
+
+    /// Generate into `{path}.tmp` and rename it once synced, so the final path only ever
+    /// holds finished files. The dataset records its progress in `{path}.progress` and
+    /// resumes from it when the progress was written for the same seed and config.
+    fn new(config: &DagConfig, epoch: usize, kind: DataKind, cache: Option<&[u8]>) -> Result<Self> {
+        let path = config.file_path(epoch, kind);
+        let tmp_path = with_suffix(&path, ".tmp");
+        let progress_path = with_suffix(&path, ".progress");
+        let cache_size = config.data_size(epoch, kind);
+        let stamp = progress_stamp(config, epoch)?;
+        let resume_row = if kind == DataKind::Dataset {
+            Self::read_progress(&tmp_path, &progress_path, &stamp, cache_size)
+        } else {
+            None
+        };
+        if kind == DataKind::Dataset && resume_row.is_none() {
+            // a crash before the first checkpoint must not resume from a stale progress
+            // over the truncated rows
+            Self::remove_progress(config, &progress_path)?;
+        }
         let file = OpenOptions::new()
             .read(true)
             .write(true)
             .create(true)
-            .open(&path)?;
-        let is_l1 = cache.is_some();
-        let cache_size = if is_l1 {
-            config.l1_cache_size
-        } else {
-            config.cache_size(epoch)
-        };
-        file.set_len(cache_size as u64 + 1)?;
+            .truncate(resume_row.is_none())
+            .open(&tmp_path)?;
+        file.set_len((HEADER_SIZE + cache_size + 1) as u64)?;
         let mut mmap = unsafe { MmapMut::map_mut(&file)? };
-        if let Some(cache) = cache {
-            generate_l1_cache(&mut mmap, cache, config.dataset_parents);
-        } else {
-            let seed = config.seed_hash(epoch * config.epoch_length + 1);
-            generate_cache(&mut mmap, &seed, config.cache_rounds);
+        match (kind, cache) {
+            (DataKind::Cache, _) => {
+                let seed = config.seed_for_epoch(epoch)?;
+                generate_cache_with(
+                    &mut mmap[HEADER_SIZE..],
+                    &seed,
+                    config.cache_rounds,
+                    config.cache_hasher.fill_fn(),
+                )?;
+            }
+            (DataKind::L1, Some(cache)) => {
+                generate_l1_cache(&mut mmap[HEADER_SIZE..], cache, config.dataset_parents)?;
+            }
+            (DataKind::Dataset, Some(cache)) => {
+                let first_row = resume_row.unwrap_or(0);
+                info!(
+                    "generating dataset of epoch {} at {:?} from row {}",
+                    epoch, path, first_row
+                );
+                Self::generate_dataset(
+                    config,
+                    &mut mmap,
+                    cache,
+                    first_row,
+                    &progress_path,
+                    &stamp,
+                )?;
+            }
+            (kind, None) => {
+                return Err(Error::InvalidConfig(format!(
+                    "{:?} is generated from the cache",
+                    kind
+                )))
+            }
         }
-        Self::flush(config, epoch, &mut mmap, is_l1)?;
+        Self::publish(config, epoch, kind, file, mmap)
+    }
+
+    /// Write the header of the generated data, then sync and rename the temporary file.
+    fn publish(
+        config: &DagConfig,
+        epoch: usize,
+        kind: DataKind,
+        file: File,
+        mut mmap: MmapMut,
+    ) -> Result<Self> {
+        let path = config.file_path(epoch, kind);
+        let data_end = mmap.len() - 1;
+        let header = FileHeader::new(config, epoch, kind, &mmap[HEADER_SIZE..data_end])?;
+        mmap[..HEADER_SIZE].copy_from_slice(&header.to_bytes());
+        mmap.flush()?;
+        file.sync_all()?;
+        fs::rename(with_suffix(&path, ".tmp"), &path)?;
+        if kind == DataKind::Dataset {
+            remove_file(&with_suffix(&path, ".progress"));
+        }
+        sync_dir(config);
+        Self::prune(config, epoch, kind);
         Ok(Self {
             epoch,
-            is_l1,
             mmap: Arc::new(mmap.make_read_only()?),
+            corrupt: Default::default(),
         })
     }
 
-    fn flush(config: &DagConfig, epoch: usize, mmap: &mut MmapMut, is_l1: bool) -> Result<()> {
-        fn is_old_cache_file(entry: &DirEntry, small_epoch: usize, is_l1: bool) -> bool {
-            let file_name = entry.file_name().to_str();
-            if file_name.is_none() {
-                return false;
-            }
-            let file_name = file_name.unwrap();
-            let s: Vec<_> = file_name.rsplitn(2, '-').collect();
-            if s.len() != 2 {
-                return true;
+    /// Rewrite an L1 cache or dataset file of the headerless format with a header, when the
+    /// items it holds match those computed from `cache`. Headerless caches can't be checked
+    /// short of generating them again, so they are regenerated.
+    fn migrate(
+        config: &DagConfig,
+        epoch: usize,
+        kind: DataKind,
+        cache: Option<&[u8]>,
+    ) -> Result<Option<Self>> {
+        let cache = match (kind, cache) {
+            (DataKind::L1 | DataKind::Dataset, Some(cache)) if cache.len() >= HASH_BYTES => cache,
+            _ => return Ok(None),
+        };
+        let path = config.file_path(epoch, kind);
+        let legacy = match File::open(&path) {
+            Ok(file) => unsafe { Mmap::map(&file)? },
+            Err(_) => return Ok(None),
+        };
+        let size = config.data_size(epoch, kind);
+        if legacy.len() != size + 1
+            || legacy[size] != 1
+            || FileHeader::from_bytes(&legacy).is_some()
+        {
+            return Ok(None);
+        }
+        let rows = size / HASH_BYTES;
+        for i in [0, rows / 2, rows.saturating_sub(1)] {
+            let item = generate_dataset_item(cache, i, config.dataset_parents);
+            if legacy[i * HASH_BYTES..(i + 1) * HASH_BYTES] != item {
+                return Ok(None);
             }
-            let old_epoch: usize = s[1].parse().unwrap_or_default();
-            let is_old = old_epoch < small_epoch;
-            if is_l1 {
-                file_name.starts_with("l1") && is_old
-            } else {
-                !file_name.starts_with("l1") && is_old
+        }
+        info!("adding a header to {:?}", path);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(with_suffix(&path, ".tmp"))?;
+        file.set_len((HEADER_SIZE + size + 1) as u64)?;
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        mmap[HEADER_SIZE..].copy_from_slice(&legacy);
+        drop(legacy);
+        Self::publish(config, epoch, kind, file, mmap).map(Some)
+    }
+
+    /// Row to resume the dataset from, when an interrupted generation left its file. The
+    /// progress is the row, little endian u64, followed by the `progress_stamp`.
+    fn read_progress(
+        tmp_path: &Path,
+        progress_path: &Path,
+        stamp: &[u8; PROGRESS_STAMP_SIZE],
+        size: usize,
+    ) -> Option<usize> {
+        let tmp_len = fs::metadata(tmp_path).ok()?.len();
+        let progress = fs::read(progress_path).ok()?;
+        if progress.len() != 8 + PROGRESS_STAMP_SIZE || progress[8..] != stamp[..] {
+            return None;
+        }
+        let row = LittleEndian::read_u64(&progress[..8]) as usize;
+        (tmp_len == (HEADER_SIZE + size + 1) as u64 && row <= size / HASH_BYTES).then_some(row)
+    }
+
+    /// Remove the progress of the dataset before its rows are generated again.
+    fn remove_progress(config: &DagConfig, progress_path: &Path) -> Result<()> {
+        match fs::remove_file(progress_path) {
+            Ok(()) => {
+                sync_dir(config);
+                Ok(())
             }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error.into()),
         }
-        mmap.flush()?;
-        let old_epoch = epoch.checked_sub(3);
-        if old_epoch.is_none() {
-            return Ok(());
+    }
+
+    fn generate_dataset(
+        config: &DagConfig,
+        mmap: &mut MmapMut,
+        cache: &[u8],
+        first_row: usize,
+        progress_path: &Path,
+        stamp: &[u8; PROGRESS_STAMP_SIZE],
+    ) -> Result<()> {
+        let size = mmap.len() - HEADER_SIZE - 1;
+        let rows = size / HASH_BYTES;
+        let mut row = first_row;
+        while row < rows {
+            let end = (row + CHECKPOINT_ROWS).min(rows);
+            let offset = HEADER_SIZE + row * HASH_BYTES;
+            let len = (end - row) * HASH_BYTES;
+            generate_dataset_rows(
+                &mut mmap[offset..offset + len],
+                row,
+                cache,
+                config.dataset_parents,
+            )?;
+            // the rows must be on disk before the progress says so
+            mmap.flush_range(offset, len)?;
+            let mut progress = File::create(progress_path)?;
+            progress.write_all(&(end as u64).to_le_bytes())?;
+            progress.write_all(stamp)?;
+            progress.sync_all()?;
+            row = end;
+        }
+        mmap[HEADER_SIZE + size] = 1;
+        Ok(())
+    }
+
+    /// Keep the files of the `caches_on_disk` most recently used epochs, the modification
+    /// time is the last use. The temporary and progress files of the other epochs are
+    /// removed too, unless a process holds their lock to generate them. Lock files are left
+    /// as another process may wait on them.
+    fn prune(config: &DagConfig, epoch: usize, kind: DataKind) {
+        // only touch files written by this config, e.g. `cache-RVN-13` or `cache-RVN-13.tmp`,
+        // return the epoch and whether it is the data file itself
+        fn file_epoch(entry: &DirEntry, prefix: &str) -> Option<(usize, bool)> {
+            let name = entry.file_name().to_str()?.strip_prefix(prefix)?;
+            let (epoch, suffix) = match name.split_once('.') {
+                Some((epoch, suffix)) => (epoch, Some(suffix)),
+                None => (name, None),
+            };
+            match suffix {
+                None => Some((epoch.parse().ok()?, true)),
+                Some("tmp" | "progress") => Some((epoch.parse().ok()?, false)),
+                Some(_) => None,
+            }
         }
-        let old_epoch = old_epoch.unwrap();
-        for old_cache_file in WalkDir::new(&config.storage_dir)
+        let prefix = format!("{}-{}-", kind.prefix(), config.name);
+        let mut data_files: Vec<(SystemTime, usize)> = vec![];
+        let mut orphans: Vec<(usize, PathBuf)> = vec![];
+        for entry in WalkDir::new(&config.storage_dir)
+            .max_depth(1)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|p| is_old_cache_file(p, old_epoch, is_l1))
-            .map(|p| p.path().to_path_buf())
         {
-            fs::remove_file(old_cache_file).unwrap_or_else(|error| match error.kind() {
-                io::ErrorKind::NotFound => (),
-                _ => warn!("Error removing stale DAG cache: {:?}", error),
-            })
+            match file_epoch(&entry, &prefix) {
+                Some((old_epoch, true)) => {
+                    if let Some(used) = entry.metadata().ok().and_then(|m| m.modified().ok()) {
+                        data_files.push((used, old_epoch));
+                    }
+                }
+                Some((old_epoch, false)) => orphans.push((old_epoch, entry.into_path())),
+                None => (),
+            }
+        }
+        data_files.retain(|(_, old_epoch)| *old_epoch != epoch);
+        data_files.sort_by(|a, b| b.cmp(a));
+        let mut retained = vec![epoch];
+        for (i, (_, old_epoch)) in data_files.into_iter().enumerate() {
+            if i + 1 < config.caches_on_disk {
+                retained.push(old_epoch);
+            } else {
+                remove_file(&config.file_path(old_epoch, kind));
+            }
+        }
+        for (old_epoch, path) in orphans {
+            if !retained.contains(&old_epoch) && !Self::is_generating(config, old_epoch, kind) {
+                remove_file(&path);
+            }
         }
-        Ok(())
+    }
+
+    /// Whether a process holds the lock file of the epoch, e.g. to generate it.
+    fn is_generating(config: &DagConfig, epoch: usize, kind: DataKind) -> bool {
+        let lock_file = match File::open(config.lock_path(epoch, kind)) {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+        let locked = lock_file.try_lock_exclusive().is_err();
+        if !locked {
+            let _ = lock_file.unlock();
+        }
+        locked
+    }
+
+    /// Whether a checksum mismatch was found after loading, the data must not be used.
+    pub fn is_corrupt(&self) -> bool {
+        self.corrupt.load(Ordering::Acquire)
     }
 
     pub fn data(&self) -> &[u8] {
         let cache_len = self.mmap.len();
-        let data: &[u8] = &self.mmap[0..cache_len - 1];
+        let data: &[u8] = &self.mmap[HEADER_SIZE..cache_len - 1];
         data
     }
 }
+
+const PROGRESS_STAMP_SIZE: usize = 64;
+
+/// Seed and config hash of the dataset of the epoch, a progress written by another config
+/// under the same name is not resumed.
+fn progress_stamp(config: &DagConfig, epoch: usize) -> Result<[u8; PROGRESS_STAMP_SIZE]> {
+    let mut stamp = [0; PROGRESS_STAMP_SIZE];
+    stamp[..32].copy_from_slice(&config.seed_for_epoch(epoch)?);
+    stamp[32..].copy_from_slice(&config_hash(config, epoch, DataKind::Dataset));
+    Ok(stamp)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Persist the renames and removals in the storage directory, not every platform can sync
+/// a directory.
+fn sync_dir(config: &DagConfig) {
+    if let Ok(dir) = File::open(&config.storage_dir) {
+        let _ = dir.sync_all();
+    }
+}
+
+fn remove_file(path: &Path) {
+    fs::remove_file(path).unwrap_or_else(|error| match error.kind() {
+        io::ErrorKind::NotFound => (),
+        _ => warn!("Error removing stale DAG cache: {:?}", error),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::internal::dag::cache::{generate_cache, generate_dataset};
+    use crate::internal::dag::config::{CacheHasher, LookupTable};
+
+    fn tiny_config() -> DagConfig {
+        DagConfig {
+            name: "RVNCRASH".to_string(),
+            revision: 23,
+            storage_dir: PathBuf::from("/tmp"),
+
+            dataset_init_bytes: 1 << 16,
+            dataset_growth_bytes: 1 << 10,
+            cache_init_bytes: 1 << 14,
+            cache_growth_bytes: 1 << 8,
+
+            dataset_sizes: LookupTable::default(),
+            cache_sizes: LookupTable::default(),
+
+            mix_bytes: 128,
+            dataset_parents: 64,
+            epoch_length: 4,
+            seed_epoch_length: 4,
+
+            cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
+            caches_count: 3,
+            caches_on_disk: 3,
+            caches_lock_mmap: false,
+
+            l1_enabled: false,
+            l1_cache_size: 4096 * 4,
+            l1_cache_num_items: 4096,
+        }
+    }
+
+    #[test]
+    fn test_restart_removes_stale_progress() {
+        let cfg = tiny_config();
+        let path = cfg.file_path(0, DataKind::Dataset);
+        let tmp_path = with_suffix(&path, ".tmp");
+        let progress_path = with_suffix(&path, ".progress");
+        let _ = fs::remove_file(&path);
+
+        // a progress of 100 rows whose temporary file was cut short, so the rows start over
+        let mut progress = 100u64.to_le_bytes().to_vec();
+        progress.extend_from_slice(&progress_stamp(&cfg, 0).unwrap());
+        fs::write(&progress_path, &progress).unwrap();
+        fs::write(&tmp_path, [0; 16]).unwrap();
+
+        // the generation fails before its first checkpoint, as a crash would
+        assert!(DataFile::new(&cfg, 0, DataKind::Dataset, Some(&[0; 16])).is_err());
+        assert!(!progress_path.exists());
+
+        let mut cache = vec![0; cfg.cache_size(0) + 1];
+        generate_cache(
+            &mut cache,
+            &cfg.seed_for_epoch(0).unwrap(),
+            cfg.cache_rounds,
+        )
+        .unwrap();
+        let cache = &cache[..cache.len() - 1];
+        let mut expected = vec![0; cfg.dataset_size(0) + 1];
+        generate_dataset(&mut expected, cache, cfg.dataset_parents).unwrap();
+
+        let data = DataFile::new(&cfg, 0, DataKind::Dataset, Some(cache)).unwrap();
+        assert_eq!(data.data(), &expected[..expected.len() - 1]);
+    }
+
+    #[test]
+    fn test_generator_lock_is_removed() {
+        let cfg = DagConfig {
+            name: "RVNGENLOCK".to_string(),
+            ..tiny_config()
+        };
+        let _ = fs::remove_file(cfg.file_path(0, DataKind::Cache));
+        DataFile::generate(&cfg, 0, DataKind::Cache, None).unwrap();
+        assert!(!GENERATOR_LOCKS
+            .lock()
+            .contains_key(&cfg.lock_path(0, DataKind::Cache)));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::Result;
+use crate::internal::dag::cache::Cache;
+use crate::internal::dag::config::{DagConfig, DataKind, HASH_BYTES, WORD_BYTES};
+use crate::internal::dag::data_file::DataFile;
+use byteorder::{ByteOrder, LittleEndian};
+
+/// The full dataset of an epoch, memory mapped from `storage_dir`.
+#[derive(Clone)]
+pub struct Dataset {
+    dataset: DataFile,
+}
+
+impl Dataset {
+    pub fn epoch(&self) -> usize {
+        self.dataset.epoch
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.dataset.data()
+    }
+
+    /// Whether the file turned out not to match its checksum after it was loaded, the
+    /// checksum of a dataset is checked in the background. `Dag::get_dataset` generates a
+    /// corrupt dataset again.
+    pub fn is_corrupt(&self) -> bool {
+        self.dataset.is_corrupt()
+    }
+
+    /// Same words as `generate_dataset_item_unit`, read from the dataset.
+    pub fn items(&self, index: usize, size: usize) -> Vec<u32> {
+        let start = index * size * HASH_BYTES;
+        let mut data = vec![0; size * HASH_BYTES / WORD_BYTES];
+        LittleEndian::read_u32_into(&self.data()[start..start + size * HASH_BYTES], &mut data);
+        data
+    }
+
+    /// Load the dataset of the cache's epoch, it is generated on all cores when missing.
+    pub fn generate(config: &DagConfig, cache: &Cache) -> Result<Self> {
+        let dataset = DataFile::generate(
+            config,
+            cache.epoch(),
+            DataKind::Dataset,
+            Some(cache.cache()),
+        )?;
+        Ok(Self { dataset })
+    }
+}
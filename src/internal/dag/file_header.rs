@@ -0,0 +1,138 @@
+use crate::error::Result;
+use crate::internal::crypto::sha256::sha256;
+use crate::internal::dag::config::{CacheHasher, DagConfig, DataKind};
+use crate::internal::dag::SeedType;
+use byteorder::{ByteOrder, LittleEndian};
+
+pub const MAGIC: [u8; 8] = *b"POWKITDF";
+pub const FORMAT_VERSION: u32 = 1;
+/// Bytes before the data, the data stays 64 bytes aligned.
+pub const HEADER_SIZE: usize = 256;
+/// Bytes of the config name kept in the header, longer names are cut.
+const NAME_BYTES: usize = 32;
+
+/// Header of the files under `storage_dir`, it tells what the data was generated
+/// from and checksums it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHeader {
+    pub version: u32,
+    pub kind: DataKind,
+    pub name: [u8; NAME_BYTES],
+    pub epoch: u64,
+    pub seed: SeedType,
+    pub config_hash: [u8; 32],
+    /// SHA256 of the data
+    pub checksum: [u8; 32],
+}
+
+impl FileHeader {
+    pub fn new(config: &DagConfig, epoch: usize, kind: DataKind, data: &[u8]) -> Result<Self> {
+        Ok(Self {
+            version: FORMAT_VERSION,
+            kind,
+            name: name_bytes(&config.name),
+            epoch: epoch as u64,
+            seed: config.seed_for_epoch(epoch)?,
+            config_hash: config_hash(config, epoch, kind),
+            checksum: sha256(data),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut b = [0u8; HEADER_SIZE];
+        b[0..8].copy_from_slice(&MAGIC);
+        LittleEndian::write_u32(&mut b[8..12], self.version);
+        b[12] = kind_byte(self.kind);
+        b[16..48].copy_from_slice(&self.name);
+        LittleEndian::write_u64(&mut b[48..56], self.epoch);
+        b[56..88].copy_from_slice(&self.seed);
+        b[88..120].copy_from_slice(&self.config_hash);
+        b[120..152].copy_from_slice(&self.checksum);
+        b
+    }
+
+    /// None unless `b` starts with a header of a known kind.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        if b.len() < HEADER_SIZE || b[0..8] != MAGIC {
+            return None;
+        }
+        let kind = match b[12] {
+            0 => DataKind::Cache,
+            1 => DataKind::L1,
+            2 => DataKind::Dataset,
+            _ => return None,
+        };
+        let mut header = Self {
+            version: LittleEndian::read_u32(&b[8..12]),
+            kind,
+            name: [0; NAME_BYTES],
+            epoch: LittleEndian::read_u64(&b[48..56]),
+            seed: [0; 32],
+            config_hash: [0; 32],
+            checksum: [0; 32],
+        };
+        header.name.copy_from_slice(&b[16..48]);
+        header.seed.copy_from_slice(&b[56..88]);
+        header.config_hash.copy_from_slice(&b[88..120]);
+        header.checksum.copy_from_slice(&b[120..152]);
+        Some(header)
+    }
+
+    /// Why the data can't be used for the epoch of the config, the checksum is left
+    /// to the caller.
+    pub fn mismatch(&self, config: &DagConfig, epoch: usize, kind: DataKind) -> Option<String> {
+        if self.version != FORMAT_VERSION {
+            return Some(format!("format version {}", self.version));
+        }
+        if self.kind != kind {
+            return Some(format!("{:?} file, expected {:?}", self.kind, kind));
+        }
+        if self.name != name_bytes(&config.name) || self.epoch != epoch as u64 {
+            return Some("written for another config name or epoch".into());
+        }
+        if config.seed_for_epoch(epoch).ok() != Some(self.seed) {
+            return Some("seed hash mismatch".into());
+        }
+        if self.config_hash != config_hash(config, epoch, kind) {
+            return Some("config hash mismatch".into());
+        }
+        None
+    }
+}
+
+/// Fingerprint of the parameters the data of the epoch depends on.
+pub fn config_hash(config: &DagConfig, epoch: usize, kind: DataKind) -> [u8; 32] {
+    let hasher = match config.cache_hasher {
+        CacheHasher::Keccak512 => 0,
+        CacheHasher::Sha3_512 => 1,
+    };
+    let fields = [
+        kind_byte(kind) as u64,
+        config.revision,
+        config.cache_size(epoch) as u64,
+        config.data_size(epoch, kind) as u64,
+        config.cache_rounds as u64,
+        hasher,
+        config.dataset_parents as u64,
+        config.epoch_length as u64,
+        config.seed_epoch_length as u64,
+    ];
+    let mut b = vec![0u8; fields.len() * 8];
+    LittleEndian::write_u64_into(&fields, &mut b);
+    sha256(&b)
+}
+
+fn kind_byte(kind: DataKind) -> u8 {
+    match kind {
+        DataKind::Cache => 0,
+        DataKind::L1 => 1,
+        DataKind::Dataset => 2,
+    }
+}
+
+fn name_bytes(name: &str) -> [u8; NAME_BYTES] {
+    let mut b = [0u8; NAME_BYTES];
+    let len = name.len().min(NAME_BYTES);
+    b[..len].copy_from_slice(&name.as_bytes()[..len]);
+    b
+}
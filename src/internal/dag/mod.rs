@@ -1,20 +1,28 @@
-use anyhow::Result;
-use parking_lot::RwLock;
-use std::collections::HashMap;
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
 
+use crate::error::{Error, Result};
 use crate::internal::dag::cache::Cache;
-use crate::internal::dag::config::DagConfig;
+use crate::internal::dag::config::{DagConfig, MAX_EPOCH};
+use crate::internal::dag::dataset::Dataset;
 
 pub mod cache;
 pub mod config;
 pub(crate) mod data_file;
+pub mod dataset;
+pub mod file_header;
+pub(crate) mod lookup;
+pub mod seed;
 
 pub type SeedType = [u8; 32];
 
 pub struct Dag {
     pub config: DagConfig,
-    /// epoch -> Cache
-    caches: RwLock<HashMap<usize, Cache>>,
+    /// at most `caches_count` caches, the most recently used last, an evicted cache is
+    /// unmapped once its last clone is dropped
+    caches: Mutex<VecDeque<Cache>>,
+    /// only the latest dataset is kept mapped
+    dataset: RwLock<Option<Dataset>>,
 }
 
 impl Dag {
@@ -22,20 +30,54 @@ impl Dag {
         Self {
             config,
             caches: Default::default(),
+            dataset: Default::default(),
         }
     }
 
+    /// Cache of the epoch, generated or loaded from `storage_dir` on first use.
     pub fn get_cache(&self, epoch: usize) -> Result<Cache> {
-        let cache = {
-            let caches = self.caches.read();
-            caches.get(&epoch).cloned()
-        };
-        if let Some(cache) = cache {
+        if epoch > MAX_EPOCH {
+            return Err(Error::EpochOutOfRange(epoch));
+        }
+        if let Some(cache) = self.touch(epoch) {
             return Ok(cache);
         }
+        self.config.validate()?;
         let new_cache = Cache::generate(epoch, &self.config)?;
-        let mut caches = self.caches.write();
-        caches.insert(epoch, new_cache.clone());
+        let mut caches = self.caches.lock();
+        // another thread may have generated it meanwhile
+        caches.retain(|c| c.epoch() != epoch);
+        caches.push_back(new_cache.clone());
+        while caches.len() > self.config.caches_count {
+            caches.pop_front();
+        }
         Ok(new_cache)
     }
+
+    /// Epochs of the caches held in memory, the least recently used first.
+    pub fn cached_epochs(&self) -> Vec<usize> {
+        self.caches.lock().iter().map(Cache::epoch).collect()
+    }
+
+    fn touch(&self, epoch: usize) -> Option<Cache> {
+        let mut caches = self.caches.lock();
+        let i = caches.iter().position(|c| c.epoch() == epoch)?;
+        let cache = caches.remove(i)?;
+        caches.push_back(cache.clone());
+        Some(cache)
+    }
+
+    /// Full dataset of the epoch, generated or loaded from `storage_dir` on first use, or
+    /// generated again once the loaded one was found corrupt.
+    pub fn get_dataset(&self, epoch: usize) -> Result<Dataset> {
+        if let Some(dataset) = self.dataset.read().as_ref() {
+            if dataset.epoch() == epoch && !dataset.is_corrupt() {
+                return Ok(dataset.clone());
+            }
+        }
+        let cache = self.get_cache(epoch)?;
+        let dataset = Dataset::generate(&self.config, &cache)?;
+        *self.dataset.write() = Some(dataset.clone());
+        Ok(dataset)
+    }
 }
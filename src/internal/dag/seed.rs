@@ -0,0 +1,60 @@
+use crate::internal::dag::SeedType;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+
+/// The seed chain, `seeds[n]` is keccak256 applied `n` times to 32 zero bytes.
+///
+/// Every config walks the same chain, only the round of an epoch differs, so a single
+/// table is shared and extended on demand.
+#[derive(Default)]
+struct SeedTable {
+    seeds: Vec<SeedType>,
+    rounds: HashMap<SeedType, usize>,
+}
+
+impl SeedTable {
+    fn extend_to(&mut self, round: usize) {
+        if self.seeds.is_empty() {
+            self.seeds.push([0; 32]);
+            self.rounds.insert([0; 32], 0);
+        }
+        while self.seeds.len() <= round {
+            let mut hasher = Keccak256::default();
+            hasher.update(self.seeds[self.seeds.len() - 1]);
+            let seed: SeedType = hasher.finalize().into();
+            self.rounds.insert(seed, self.seeds.len());
+            self.seeds.push(seed);
+        }
+    }
+}
+
+static SEEDS: Lazy<RwLock<SeedTable>> = Lazy::new(Default::default);
+
+/// Seed after `round` keccak256 rounds, the table grows up to `round`, so callers bound it
+/// with `MAX_EPOCH` through `DagConfig::seed_for_epoch`.
+pub(crate) fn seed(round: usize) -> SeedType {
+    if let Some(seed) = SEEDS.read().seeds.get(round) {
+        return *seed;
+    }
+    let mut table = SEEDS.write();
+    table.extend_to(round);
+    table.seeds[round]
+}
+
+/// Round of a seed, the chain is extended up to `max_round` to find it.
+pub fn round(seed: &SeedType, max_round: usize) -> Option<usize> {
+    {
+        let table = SEEDS.read();
+        if let Some(round) = table.rounds.get(seed) {
+            return Some(*round);
+        }
+        if table.seeds.len() > max_round {
+            return None;
+        }
+    }
+    let mut table = SEEDS.write();
+    table.extend_to(max_round);
+    table.rounds.get(seed).copied()
+}
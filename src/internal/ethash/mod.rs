@@ -0,0 +1,73 @@
+use crate::internal::crypto::fnv::{fnv1, fnv_hash};
+use crate::internal::crypto::keccak::{keccak256, keccak512};
+use crate::internal::dag::cache::generate_dataset_item_unit;
+use crate::internal::dag::config::{HASH_BYTES, WORD_BYTES};
+use crate::internal::progpow::convutil::{bytes_to_u32array, u32array_to_bytes};
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Width of the mix in bytes.
+pub const MIX_BYTES: usize = 128;
+/// Number of accesses in the hashimoto loop.
+pub const LOOP_ACCESSES: usize = 64;
+
+/// Aggregate data from the full dataset in order to produce the final value
+/// for a particular header hash and nonce, return mix, digest.
+pub fn hashimoto<F: Fn(usize) -> Vec<u32>>(
+    hash: &[u8],
+    nonce: u64,
+    dataset_size: usize,
+    lookup: F,
+) -> (Vec<u8>, Vec<u8>) {
+    // Calculate the number of theoretical rows (we use one buffer nonetheless)
+    let rows = (dataset_size / MIX_BYTES) as u32;
+
+    // Combine header+nonce into a 40 byte seed
+    let mut seed = [0u8; 40];
+    seed[0..32].copy_from_slice(&hash[0..32]);
+    LittleEndian::write_u64(&mut seed[32..], nonce);
+    let seed = keccak512(&seed);
+    let seed_head = LittleEndian::read_u32(&seed);
+
+    // Start the mix with replicated seed
+    let hash_words = HASH_BYTES / WORD_BYTES;
+    let mut mix: Vec<u32> = (0..MIX_BYTES / WORD_BYTES)
+        .map(|i| LittleEndian::read_u32(&seed[(i % hash_words) * 4..]))
+        .collect();
+
+    // Mix in random dataset nodes
+    for i in 0..LOOP_ACCESSES {
+        let parent = fnv1(i as u32 ^ seed_head, mix[i % mix.len()]) % rows;
+        let data = lookup(parent as usize);
+        fnv_hash(&mut mix, &data);
+    }
+
+    // Compress mix
+    let cmix: Vec<u32> = mix
+        .chunks(4)
+        .map(|w| fnv1(fnv1(fnv1(w[0], w[1]), w[2]), w[3]))
+        .collect();
+    let digest = u32array_to_bytes(&cmix);
+
+    let mut result = seed;
+    result.extend_from_slice(&digest);
+    (digest, keccak256(&result))
+}
+
+/// Aggregate data from the light cache, the dataset items are generated on the fly.
+pub fn hashimoto_light(
+    hash: &[u8],
+    nonce: u64,
+    dataset_size: usize,
+    cache: &[u8],
+    dataset_parents: usize,
+) -> (Vec<u8>, Vec<u8>) {
+    let lookup =
+        |index| generate_dataset_item_unit(cache, index, MIX_BYTES / HASH_BYTES, dataset_parents);
+    hashimoto(hash, nonce, dataset_size, lookup)
+}
+
+/// Aggregate data from a fully generated dataset.
+pub fn hashimoto_full(hash: &[u8], nonce: u64, dataset: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let lookup = |index| bytes_to_u32array(&dataset[index * MIX_BYTES..(index + 1) * MIX_BYTES]);
+    hashimoto(hash, nonce, dataset.len(), lookup)
+}
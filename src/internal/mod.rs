@@ -1,4 +1,6 @@
 pub mod common;
 pub mod crypto;
 pub mod dag;
+pub mod ethash;
 pub mod progpow;
+pub mod rlp;
@@ -32,7 +32,7 @@ pub fn round<F: Fn(usize) -> Vec<u32>>(
     l1: &[u8],
 ) {
     let mut state = MixRngState::new(seed, cfg.register_count as u32);
-    let num_items = (dataset_size / (2 * 128)) as u32;
+    let num_items = (dataset_size / cfg.dag_load_bytes()) as u32;
     let item_index = mix[r % (cfg.lane_count)][0] % num_items;
     let item = lookup(item_index as usize);
     let num_words_per_lane = item.len() / cfg.lane_count;
@@ -96,16 +96,16 @@ pub fn hash<F: Fn(usize) -> Vec<u32>>(
     lookup: F,
     l1: &[u8],
 ) -> Vec<u8> {
-    let mut mix = init_mix(seed, cfg.lane_count, cfg.register_count as usize);
+    let mut mix = init_mix(seed, cfg.lane_count, cfg.register_count);
     let number = height / cfg.period_length as u64;
     for i in 0..cfg.round_count {
         round(cfg, number, i, &mut mix, dataset_size, &lookup, l1);
     }
     let mut lane_hash = vec![0; cfg.lane_count];
-    for l in 0..lane_hash.len() {
-        lane_hash[l] = FNV_OFFSET_BASIS;
-        for i in 0..cfg.register_count {
-            lane_hash[l] = fnv1_a(lane_hash[l], mix[l][i]);
+    for (l, hash) in lane_hash.iter_mut().enumerate() {
+        *hash = FNV_OFFSET_BASIS;
+        for word in mix[l].iter().take(cfg.register_count) {
+            *hash = fnv1_a(*hash, *word);
         }
     }
     let num_words = 8;
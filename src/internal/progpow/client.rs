@@ -1 +1,101 @@
+use crate::algorithm::PowAlgorithm;
+use crate::error::{Error, Result};
+use crate::internal::dag::cache::{generate_dataset_item_unit, Cache};
+use crate::internal::dag::config::DagConfig;
+use crate::internal::dag::Dag;
+use crate::internal::progpow::variant::Variant;
 
+/// Client of any ProgPoW variant, the dataset items are calculated from the cache unless
+/// computed from the full dataset with `compute_full`.
+pub struct Client {
+    variant: Variant,
+    dag: Dag,
+}
+
+impl Client {
+    pub fn new(variant: Variant) -> Self {
+        let dag = Dag::new(variant.dag.clone());
+        Self { variant, dag }
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    pub fn config(&self) -> &DagConfig {
+        &self.dag.config
+    }
+
+    pub fn get_cache(&self, epoch: usize) -> Result<Cache> {
+        self.dag.get_cache(epoch)
+    }
+
+    // return mix, digest
+    pub fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        let epoch = self.dag.config.calc_epoch(height as usize);
+        let cache = self.dag.get_cache(epoch)?;
+        self.compute_with(&cache, hash, height, nonce)
+    }
+
+    /// Same as `compute` with the cache of the height's epoch already at hand.
+    pub fn compute_with(
+        &self,
+        cache: &Cache,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let epoch = self.dag.config.calc_epoch(height as usize);
+        if cache.epoch() != epoch {
+            return Err(Error::InvalidInput(format!(
+                "cache of epoch {}, height {} is in epoch {}",
+                cache.epoch(),
+                height,
+                epoch
+            )));
+        }
+        let l1 = cache.l1().ok_or(Error::L1Missing)?;
+        let items_per_load = self.variant.dag_items_per_load();
+        let look_up = |index| {
+            generate_dataset_item_unit(
+                cache.cache(),
+                index,
+                items_per_load,
+                self.dag.config.dataset_parents,
+            )
+        };
+        let dataset_size = self.dag.config.dataset_size(cache.epoch());
+
+        self.variant
+            .hash(hash, height, nonce, dataset_size, look_up, l1)
+    }
+
+    // return mix, digest, the dataset items are read from the full dataset,
+    // which is generated or loaded on first use
+    pub fn compute_full(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let epoch = self.dag.config.calc_epoch(height as usize);
+        let dataset = self.dag.get_dataset(epoch)?;
+        let cache = self.dag.get_cache(epoch)?;
+        let l1 = cache.l1().ok_or(Error::L1Missing)?;
+        let items_per_load = self.variant.dag_items_per_load();
+        let look_up = |index| dataset.items(index, items_per_load);
+
+        self.variant
+            .hash(hash, height, nonce, dataset.data().len(), look_up, l1)
+    }
+}
+
+impl PowAlgorithm for Client {
+    fn dag_config(&self, _height: u64) -> &DagConfig {
+        self.config()
+    }
+
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        Client::compute(self, hash, height, nonce)
+    }
+}
@@ -1,6 +1,19 @@
 pub const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
 
+/// ProgPoW spec revision, they differ in the keccak_f800 passes around the mix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Revision {
+    /// keccak(header, nonce) seeds the mix, keccak(header, seed, mix) is the final hash
+    V092,
+    /// same keccak passes as 0.9.2 with a shorter period and fewer operations
+    V093,
+    /// the initial keccak state is carried over to the final hash, with padding
+    V094,
+}
+
+#[derive(Debug, Clone)]
 pub struct Config {
+    pub revision: Revision,
     pub period_length: usize,
     pub dag_loads: usize,
     pub cache_bytes: usize,
@@ -12,8 +25,26 @@ pub struct Config {
 }
 
 impl Config {
+    pub fn progpow092() -> Self {
+        Config {
+            revision: Revision::V092,
+            period_length: 50,
+            round_cache_accesses: 12,
+            round_math_operations: 20,
+            ..Self::progpow094()
+        }
+    }
+
+    pub fn progpow093() -> Self {
+        Config {
+            revision: Revision::V093,
+            ..Self::progpow094()
+        }
+    }
+
     pub fn progpow094() -> Self {
         Config {
+            revision: Revision::V094,
             period_length: 10,
             dag_loads: 4,
             cache_bytes: 16 * 1024,
@@ -24,4 +55,27 @@ impl Config {
             round_math_operations: 18,
         }
     }
+
+    /// Bytes of the DAG read by all the lanes in a round.
+    pub fn dag_load_bytes(&self) -> usize {
+        self.lane_count * self.dag_loads * std::mem::size_of::<u32>()
+    }
+
+    /// KawPow is ProgPoW 0.9.4 with a period of 3 blocks.
+    pub fn kawpow() -> Self {
+        Config {
+            period_length: 3,
+            ..Self::progpow094()
+        }
+    }
+
+    /// MeowPow runs a shorter program that changes every 6 blocks.
+    pub fn meowpow() -> Self {
+        Config {
+            period_length: 6,
+            round_cache_accesses: 6,
+            round_math_operations: 9,
+            ..Self::progpow094()
+        }
+    }
 }
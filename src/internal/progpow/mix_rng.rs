@@ -35,8 +35,8 @@ impl MixRngState {
         let jcong = fnv1_a(jsr, (seed >> 32) as u32);
 
         let mut rng = Kiss99::new(z, w, jsr, jcong);
-        let mut src_seq: Vec<u32> = (0..size).into_iter().collect();
-        let mut dst_seq: Vec<u32> = (0..size).into_iter().collect();
+        let mut src_seq: Vec<u32> = (0..size).collect();
+        let mut dst_seq: Vec<u32> = (0..size).collect();
         for i in (2..=size).rev() {
             let index = i as usize - 1;
 
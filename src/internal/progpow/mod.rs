@@ -1,6 +1,6 @@
 use crate::internal::crypto::keccak::keccak_f800;
 use crate::internal::progpow;
-use crate::internal::progpow::config::Config;
+use crate::internal::progpow::config::{Config, Revision};
 use byteorder::{ByteOrder, LittleEndian};
 
 pub mod algorithm;
@@ -10,6 +10,21 @@ pub mod convutil;
 pub mod kiss99;
 pub mod math;
 pub mod mix_rng;
+pub mod variant;
+
+/// Keccak padding of the initial pass of the ProgPoW 0.9.4 spec, KawPow and its forks
+/// replace the padding of both passes with a name.
+pub const PROGPOW_094: [u32; 15] = [
+    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x80008081, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+];
+
+/// Keccak padding of the final pass of the ProgPoW 0.9.4 spec, it absorbs 16 words instead
+/// of 10, so the padding starts at `state[17]`.
+pub const PROGPOW_094_FINAL: [u32; 15] = [
+    0x00000000, 0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x80008081, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+];
 
 pub const RAVEN_COIN_KAWPOW: [u32; 15] = [
     0x00000072, //R
@@ -29,34 +44,146 @@ pub const RAVEN_COIN_KAWPOW: [u32; 15] = [
     0x00000057, //W
 ];
 
-pub fn initialize(hash: &[u8], nonce: u64) -> ([u32; 25], u64) {
+pub const EVRMORE_PROGPOW: [u32; 15] = [
+    0x00000045, //E
+    0x00000056, //V
+    0x00000052, //R
+    0x0000004D, //M
+    0x0000004F, //O
+    0x00000052, //R
+    0x00000045, //E
+    0x0000002D, //-
+    0x00000050, //P
+    0x00000052, //R
+    0x0000004F, //O
+    0x00000047, //G
+    0x00000050, //P
+    0x0000004F, //O
+    0x00000057, //W
+];
+
+pub const MEOWCOIN_MEOWPOW: [u32; 15] = [
+    0x0000004D, //M
+    0x00000045, //E
+    0x0000004F, //O
+    0x00000057, //W
+    0x00000043, //C
+    0x0000004F, //O
+    0x00000049, //I
+    0x0000004E, //N
+    0x0000004D, //M
+    0x00000045, //E
+    0x0000004F, //O
+    0x00000057, //W
+    0x00000050, //P
+    0x0000004F, //O
+    0x00000057, //W
+];
+
+pub fn initialize(hash: &[u8], nonce: u64, padding: &[u32; 15]) -> ([u32; 25], u64) {
     let mut seed: [u32; 25] = [0; 25];
     for i in 0..8 {
         seed[i] = LittleEndian::read_u32(&hash[i * 4..]);
     }
     seed[8] = nonce as u32;
     seed[9] = (nonce >> 32) as u32;
-    seed[10..25].copy_from_slice(&RAVEN_COIN_KAWPOW[..(25 - 10)]);
+    seed[10..25].copy_from_slice(&padding[..(25 - 10)]);
     keccak_f800(&mut seed);
     let seed_head = seed[0] as u64 + ((seed[1] as u64) << 32);
     (seed, seed_head)
 }
 
-pub fn finalize(seed: [u32; 25], mix_hash: &[u8]) -> Vec<u8> {
+/// Final keccak pass, only the first 9 words of the padding fit after the seed and mix.
+pub fn finalize(seed: [u32; 25], mix_hash: &[u8], padding: &[u32; 15]) -> Vec<u8> {
     let mut state: [u32; 25] = [0; 25];
     for i in 0..8 {
         state[i] = seed[i];
         state[i + 8] = LittleEndian::read_u32(&mix_hash[i * 4..i * 4 + 4]);
     }
-    // for i in 16..25 {
-    //     state[i] = RAVEN_COIN_KAWPOW[i - 16]
-    // }
-    state[16..25].copy_from_slice(&RAVEN_COIN_KAWPOW[..(25 - 16)]);
+    state[16..25].copy_from_slice(&padding[..(25 - 16)]);
+    keccak_f800(&mut state);
+    convutil::u32array_to_bytes(&state[0..8])
+}
+
+/// Final hash of ProgPoW 0.9.4 with the given paddings from a claimed mix, without the DAG.
+pub fn padded_final_hash(
+    initial_padding: &[u32; 15],
+    final_padding: &[u32; 15],
+    hash: &[u8],
+    nonce: u64,
+    mix_hash: &[u8],
+) -> Vec<u8> {
+    let (seed, _) = initialize(hash, nonce, initial_padding);
+    finalize(seed, mix_hash, final_padding)
+}
+
+/// Seed of the ProgPoW 0.9.2 and 0.9.3 mix: keccak(header, nonce) without padding,
+/// byte 0 of the hash is the most significant byte of the seed.
+pub fn hash_seed(hash: &[u8], nonce: u64) -> u64 {
+    let mut state: [u32; 25] = [0; 25];
+    for i in 0..8 {
+        state[i] = LittleEndian::read_u32(&hash[i * 4..]);
+    }
+    state[8] = nonce as u32;
+    state[9] = (nonce >> 32) as u32;
+    keccak_f800(&mut state);
+    ((state[0].swap_bytes() as u64) << 32) | state[1].swap_bytes() as u64
+}
+
+/// Final hash of ProgPoW 0.9.2 and 0.9.3: keccak(header, seed, mix) without padding.
+pub fn final_hash(hash: &[u8], seed: u64, mix_hash: &[u8]) -> Vec<u8> {
+    let mut state: [u32; 25] = [0; 25];
+    for i in 0..8 {
+        state[i] = LittleEndian::read_u32(&hash[i * 4..]);
+        state[i + 10] = LittleEndian::read_u32(&mix_hash[i * 4..]);
+    }
+    state[8] = seed as u32;
+    state[9] = (seed >> 32) as u32;
     keccak_f800(&mut state);
     convutil::u32array_to_bytes(&state[0..8])
 }
 
-pub fn kawpow<F: Fn(usize) -> Vec<u32>>(
+/// ProgPoW as specified by the given revision, return mix, digest. The paddings are only
+/// absorbed from 0.9.4 on, `PROGPOW_094` and `PROGPOW_094_FINAL` for the spec.
+#[allow(clippy::too_many_arguments)]
+pub fn hash<F: Fn(usize) -> Vec<u32>>(
+    cfg: &Config,
+    initial_padding: &[u32; 15],
+    final_padding: &[u32; 15],
+    hash: &[u8],
+    height: u64,
+    nonce: u64,
+    dataset_size: usize,
+    lookup: F,
+    l1: &[u8],
+) -> (Vec<u8>, Vec<u8>) {
+    match cfg.revision {
+        Revision::V092 | Revision::V093 => {
+            let seed = hash_seed(hash, nonce);
+            let mix_hash = progpow::algorithm::hash(cfg, height, seed, dataset_size, lookup, l1);
+            let digest = final_hash(hash, seed, &mix_hash);
+            (mix_hash, digest)
+        }
+        Revision::V094 => padded_hash(
+            cfg,
+            initial_padding,
+            final_padding,
+            hash,
+            height,
+            nonce,
+            dataset_size,
+            lookup,
+            l1,
+        ),
+    }
+}
+
+/// ProgPoW 0.9.4 with the keccak paddings replaced, return mix, digest.
+#[allow(clippy::too_many_arguments)]
+pub fn padded_hash<F: Fn(usize) -> Vec<u32>>(
+    cfg: &Config,
+    initial_padding: &[u32; 15],
+    final_padding: &[u32; 15],
     hash: &[u8],
     height: u64,
     nonce: u64,
@@ -64,18 +191,8 @@ pub fn kawpow<F: Fn(usize) -> Vec<u32>>(
     lookup: F,
     l1: &[u8],
 ) -> (Vec<u8>, Vec<u8>) {
-    let cfg = Config {
-        period_length: 3,
-        dag_loads: 4,
-        cache_bytes: 16 * 1024,
-        lane_count: 16,
-        register_count: 32,
-        round_count: 64,
-        round_cache_accesses: 11,
-        round_math_operations: 18,
-    };
-    let (seed, seed_head) = initialize(hash, nonce);
-    let mix_hash = progpow::algorithm::hash(&cfg, height, seed_head, dataset_size, lookup, l1);
-    let digest = progpow::finalize(seed, &mix_hash);
+    let (seed, seed_head) = initialize(hash, nonce, initial_padding);
+    let mix_hash = progpow::algorithm::hash(cfg, height, seed_head, dataset_size, lookup, l1);
+    let digest = progpow::finalize(seed, &mix_hash, final_padding);
     (mix_hash, digest)
 }
@@ -0,0 +1,160 @@
+use crate::error::{Error, Result};
+use crate::internal::dag::config::{CacheHasher, DagConfig, LookupTable, HASH_BYTES};
+use crate::internal::dag::lookup::{CACHE_SIZES, DATASET_SIZES};
+use crate::internal::progpow;
+use crate::internal::progpow::config::{Config, Revision};
+use crate::internal::progpow::{EVRMORE_PROGPOW, MEOWCOIN_MEOWPOW, RAVEN_COIN_KAWPOW};
+use std::path::PathBuf;
+
+/// Everything that tells a ProgPoW fork apart, a chain reusing one of the presets
+/// with another DAG (e.g. Neoxa or Clore on KawPow) only overrides `dag`.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub progpow: Config,
+    /// keccak padding of the initial hash, unused before ProgPoW 0.9.4
+    pub initial_padding: [u32; 15],
+    /// keccak padding of the final hash, only its first 9 words are absorbed
+    pub final_padding: [u32; 15],
+    pub dag: DagConfig,
+}
+
+impl Variant {
+    /// 64 bytes dataset items read per round.
+    pub fn dag_items_per_load(&self) -> usize {
+        self.progpow.dag_load_bytes() / HASH_BYTES
+    }
+
+    // return mix, digest
+    pub fn hash<F: Fn(usize) -> Vec<u32>>(
+        &self,
+        hash: &[u8],
+        height: u64,
+        nonce: u64,
+        dataset_size: usize,
+        lookup: F,
+        l1: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        check_len("header hash", hash)?;
+        if l1.len() < self.progpow.cache_bytes {
+            return Err(Error::InvalidConfig(format!(
+                "l1 cache of {} bytes, progpow reads {}",
+                l1.len(),
+                self.progpow.cache_bytes
+            )));
+        }
+        if dataset_size < self.progpow.dag_load_bytes() {
+            return Err(Error::InvalidConfig(format!(
+                "dataset of {} bytes, progpow loads {} per round",
+                dataset_size,
+                self.progpow.dag_load_bytes()
+            )));
+        }
+        Ok(progpow::hash(
+            &self.progpow,
+            &self.initial_padding,
+            &self.final_padding,
+            hash,
+            height,
+            nonce,
+            dataset_size,
+            lookup,
+            l1,
+        ))
+    }
+
+    /// Digest of a claimed 32 bytes mix, only keccak is computed, the DAG is not touched.
+    pub fn final_hash(&self, hash: &[u8], nonce: u64, mix_hash: &[u8]) -> Result<Vec<u8>> {
+        check_len("header hash", hash)?;
+        check_len("mix hash", mix_hash)?;
+        let digest = match self.progpow.revision {
+            Revision::V092 | Revision::V093 => {
+                progpow::final_hash(hash, progpow::hash_seed(hash, nonce), mix_hash)
+            }
+            Revision::V094 => progpow::padded_final_hash(
+                &self.initial_padding,
+                &self.final_padding,
+                hash,
+                nonce,
+                mix_hash,
+            ),
+        };
+        Ok(digest)
+    }
+
+    pub fn raven_coin(storage_dir: PathBuf) -> Self {
+        Self {
+            progpow: Config::kawpow(),
+            initial_padding: RAVEN_COIN_KAWPOW,
+            final_padding: RAVEN_COIN_KAWPOW,
+            dag: DagConfig {
+                name: "RVN".into(),
+                revision: 23,
+                storage_dir,
+
+                dataset_init_bytes: 1 << 30,
+                dataset_growth_bytes: 1 << 23,
+                cache_init_bytes: 1 << 24,
+                cache_growth_bytes: 1 << 17,
+
+                cache_sizes: LookupTable::new(CACHE_SIZES.to_vec()),
+                dataset_sizes: LookupTable::new(DATASET_SIZES.to_vec()),
+
+                mix_bytes: 128,
+                dataset_parents: 512,
+                epoch_length: 7500,
+                seed_epoch_length: 7500,
+
+                cache_rounds: 3,
+                cache_hasher: CacheHasher::Keccak512,
+                caches_count: 3,
+                caches_on_disk: 3,
+                caches_lock_mmap: false,
+
+                l1_enabled: true,
+                l1_cache_size: 4096 * 4,
+                l1_cache_num_items: 4096,
+            },
+        }
+    }
+
+    pub fn evrmore(storage_dir: PathBuf) -> Self {
+        Self {
+            progpow: Config::kawpow(),
+            initial_padding: EVRMORE_PROGPOW,
+            final_padding: EVRMORE_PROGPOW,
+            dag: DagConfig {
+                name: "EVR".into(),
+                dataset_init_bytes: 3 << 30,
+                cache_sizes: LookupTable::default(),
+                dataset_sizes: LookupTable::default(),
+                dataset_parents: 256,
+                epoch_length: 12000,
+                seed_epoch_length: 12000,
+                ..Self::raven_coin(storage_dir).dag
+            },
+        }
+    }
+
+    pub fn meowcoin(storage_dir: PathBuf) -> Self {
+        Self {
+            progpow: Config::meowpow(),
+            initial_padding: MEOWCOIN_MEOWPOW,
+            final_padding: MEOWCOIN_MEOWPOW,
+            dag: DagConfig {
+                name: "MEWC".into(),
+                ..Self::raven_coin(storage_dir).dag
+            },
+        }
+    }
+}
+
+fn check_len(name: &str, b: &[u8]) -> Result<()> {
+    if b.len() != 32 {
+        return Err(Error::InvalidInput(format!(
+            "{} of {} bytes, expected 32",
+            name,
+            b.len()
+        )));
+    }
+    Ok(())
+}
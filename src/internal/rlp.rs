@@ -0,0 +1,235 @@
+use crate::error::{Error, Result};
+
+/// A decoded RLP item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    List(Vec<Rlp<'a>>),
+}
+
+impl<'a> Rlp<'a> {
+    pub fn as_bytes(&self) -> Result<&'a [u8]> {
+        match self {
+            Rlp::Bytes(b) => Ok(b),
+            Rlp::List(_) => Err(Error::InvalidInput(
+                "rlp: expected bytes, got a list".into(),
+            )),
+        }
+    }
+
+    pub fn as_list(&self) -> Result<&[Rlp<'a>]> {
+        match self {
+            Rlp::List(items) => Ok(items),
+            Rlp::Bytes(_) => Err(Error::InvalidInput(
+                "rlp: expected a list, got bytes".into(),
+            )),
+        }
+    }
+
+    pub fn as_fixed<const N: usize>(&self) -> Result<[u8; N]> {
+        let b = self.as_bytes()?;
+        if b.len() != N {
+            return Err(Error::InvalidInput(format!(
+                "rlp: expected {} bytes, got {}",
+                N,
+                b.len()
+            )));
+        }
+        let mut out = [0u8; N];
+        out.copy_from_slice(b);
+        Ok(out)
+    }
+
+    pub fn as_u64(&self) -> Result<u64> {
+        let b = self.as_uint(8)?;
+        Ok(b.iter().fold(0, |acc, byte| (acc << 8) | *byte as u64))
+    }
+
+    /// Big endian integer of at most `max_len` bytes without leading zeros.
+    pub fn as_uint(&self, max_len: usize) -> Result<&'a [u8]> {
+        let b = self.as_bytes()?;
+        if b.len() > max_len {
+            return Err(Error::InvalidInput(format!(
+                "rlp: integer of {} bytes overflows {} bytes",
+                b.len(),
+                max_len
+            )));
+        }
+        if b.first() == Some(&0) {
+            return Err(Error::InvalidInput("rlp: integer with leading zero".into()));
+        }
+        Ok(b)
+    }
+}
+
+/// Lists nested deeper than this are rejected, the recursion must not overflow the stack
+/// on crafted input.
+pub const MAX_DEPTH: usize = 64;
+
+/// Decode exactly one item spanning the whole input.
+pub fn decode(b: &[u8]) -> Result<Rlp<'_>> {
+    let (item, rest) = decode_item(b, 0)?;
+    if !rest.is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "rlp: {} trailing bytes",
+            rest.len()
+        )));
+    }
+    Ok(item)
+}
+
+fn decode_item(b: &[u8], depth: usize) -> Result<(Rlp<'_>, &[u8])> {
+    let prefix = match b.first() {
+        Some(prefix) => *prefix,
+        None => return Err(Error::InvalidInput("rlp: unexpected end of input".into())),
+    };
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Ok((Rlp::Bytes(&b[..1]), &b[1..])),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => {
+            let len_len = (prefix - 0xb7) as usize;
+            (false, 1 + len_len, decode_length(&b[1..], len_len)?)
+        }
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => {
+            let len_len = (prefix - 0xf7) as usize;
+            (true, 1 + len_len, decode_length(&b[1..], len_len)?)
+        }
+    };
+    if b.len() - offset < len {
+        return Err(Error::InvalidInput(format!(
+            "rlp: item of {} bytes exceeds the input",
+            len
+        )));
+    }
+    let (payload, rest) = b[offset..].split_at(len);
+    if !is_list {
+        if len == 1 && payload[0] < 0x80 {
+            return Err(Error::InvalidInput(
+                "rlp: single byte not encoded as itself".into(),
+            ));
+        }
+        return Ok((Rlp::Bytes(payload), rest));
+    }
+    if depth == MAX_DEPTH {
+        return Err(Error::InvalidInput(format!(
+            "rlp: lists nested deeper than {}",
+            MAX_DEPTH
+        )));
+    }
+    let mut items = vec![];
+    let mut payload = payload;
+    while !payload.is_empty() {
+        let (item, remaining) = decode_item(payload, depth + 1)?;
+        items.push(item);
+        payload = remaining;
+    }
+    Ok((Rlp::List(items), rest))
+}
+
+fn decode_length(b: &[u8], len_len: usize) -> Result<usize> {
+    if b.len() < len_len || len_len > 8 {
+        return Err(Error::InvalidInput("rlp: invalid length prefix".into()));
+    }
+    if b[0] == 0 {
+        return Err(Error::InvalidInput("rlp: length with leading zero".into()));
+    }
+    let len = b[..len_len]
+        .iter()
+        .fold(0usize, |acc, byte| (acc << 8) | *byte as usize);
+    if len <= 55 {
+        return Err(Error::InvalidInput(
+            "rlp: long form used for a short item".into(),
+        ));
+    }
+    Ok(len)
+}
+
+fn encode_length(len: usize, offset: u8, out: &mut Vec<u8>) {
+    if len <= 55 {
+        out.push(offset + len as u8);
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let skip = len_bytes.iter().take_while(|b| **b == 0).count();
+        out.push(offset + 55 + (len_bytes.len() - skip) as u8);
+        out.extend_from_slice(&len_bytes[skip..]);
+    }
+}
+
+pub fn encode_bytes(b: &[u8], out: &mut Vec<u8>) {
+    if b.len() == 1 && b[0] < 0x80 {
+        out.push(b[0]);
+    } else {
+        encode_length(b.len(), 0x80, out);
+        out.extend_from_slice(b);
+    }
+}
+
+/// Encode a big endian integer, leading zeros are stripped.
+pub fn encode_uint(b: &[u8], out: &mut Vec<u8>) {
+    let skip = b.iter().take_while(|b| **b == 0).count();
+    encode_bytes(&b[skip..], out);
+}
+
+pub fn encode_u64(v: u64, out: &mut Vec<u8>) {
+    encode_uint(&v.to_be_bytes(), out);
+}
+
+/// Wrap already encoded items into a list.
+pub fn encode_list(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 9);
+    encode_length(payload.len(), 0xc0, &mut out);
+    out.extend_from_slice(payload);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rlp() {
+        let mut payload = vec![];
+        encode_bytes(b"cat", &mut payload);
+        encode_bytes(b"dog", &mut payload);
+        let list = encode_list(&payload);
+        assert_eq!(hex::encode(&list), "c88363617483646f67");
+        assert_eq!(
+            decode(&list).unwrap(),
+            Rlp::List(vec![Rlp::Bytes(b"cat"), Rlp::Bytes(b"dog")])
+        );
+
+        let mut out = vec![];
+        encode_u64(0, &mut out);
+        encode_u64(15, &mut out);
+        encode_u64(1024, &mut out);
+        assert_eq!(hex::encode(&out), "800f820400");
+
+        let long = [0xaa; 56];
+        let mut out = vec![];
+        encode_bytes(&long, &mut out);
+        assert_eq!(&out[..2], &[0xb8, 56]);
+        assert_eq!(decode(&out).unwrap(), Rlp::Bytes(&long));
+
+        assert!(decode(&[0x81, 0x05]).is_err());
+        assert!(decode(&[0x83, 0x01]).is_err());
+        assert!(decode(&[0x0f, 0x0f]).is_err());
+        assert!(decode(&[0x82, 0x00, 0x01]).unwrap().as_u64().is_err());
+    }
+
+    #[test]
+    fn test_rlp_depth() {
+        let nested = |depth| {
+            let mut list = encode_list(&[]);
+            for _ in 0..depth {
+                list = encode_list(&list);
+            }
+            list
+        };
+        assert!(decode(&nested(MAX_DEPTH - 1)).is_ok());
+        assert!(matches!(
+            decode(&nested(MAX_DEPTH)),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+}
@@ -1,68 +1,292 @@
-use crate::internal::dag::cache::generate_dataset_item_unit;
-use crate::internal::dag::config::{DagConfig, LookupTable};
-use crate::internal::dag::Dag;
-use crate::internal::progpow::kawpow;
-use crate::kawpow::lookup::{CACHE_SIZES, DATASET_SIZES};
-use anyhow::Result;
+use crate::algorithm::{PowAlgorithm, Verification};
+use crate::error::{Error, Result};
+use crate::internal::dag::cache::Cache;
+use crate::internal::dag::config::DagConfig;
+use crate::internal::progpow;
+use crate::internal::progpow::config::Config;
+use crate::internal::progpow::variant::Variant;
+use crate::internal::progpow::RAVEN_COIN_KAWPOW;
+use crate::kawpow::header::BlockHeader;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct Client {
-    dag: Dag,
+    client: progpow::client::Client,
+}
+
+/// A share of a batch verification.
+#[derive(Debug, Clone)]
+pub struct Share {
+    pub header_hash: [u8; 32],
+    pub height: u64,
+    pub nonce: u64,
+    pub mix: Vec<u8>,
+    /// 256-bit big endian boundary
+    pub target: [u8; 32],
+}
+
+/// Outcome of `Client::verify_batch`.
+#[derive(Debug)]
+pub struct BatchVerification {
+    /// one result per share, in input order
+    pub results: Vec<Result<Verification>>,
+    /// number of distinct epochs, i.e. caches fetched
+    pub epochs: usize,
+    pub elapsed: Duration,
+}
+
+impl BatchVerification {
+    pub fn valid_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| matches!(r, Ok(Verification::Valid)))
+            .count()
+    }
 }
 
 impl Client {
+    /// KawPow over the given DAG.
     pub fn new(cfg: DagConfig) -> Self {
-        let dag = Dag::new(cfg);
-        Self { dag }
+        Self::from_variant(Variant {
+            progpow: Config::kawpow(),
+            initial_padding: RAVEN_COIN_KAWPOW,
+            final_padding: RAVEN_COIN_KAWPOW,
+            dag: cfg,
+        })
+    }
+
+    pub fn from_variant(variant: Variant) -> Self {
+        let client = progpow::client::Client::new(variant);
+        Self { client }
     }
 
     pub fn new_raven_coin(storage_dir: PathBuf) -> Self {
-        let cfg = DagConfig {
-            name: "RVN".into(),
-            revision: 23,
-            storage_dir,
-
-            dataset_init_bytes: 1 << 30,
-            dataset_growth_bytes: 1 << 23,
-            cache_init_bytes: 1 << 24,
-            cache_growth_bytes: 1 << 17,
-
-            cache_sizes: LookupTable::new(CACHE_SIZES.to_vec()),
-            dataset_sizes: LookupTable::new(DATASET_SIZES.to_vec()),
-
-            mix_bytes: 128,
-            dataset_parents: 512,
-            epoch_length: 7500,
-            seed_epoch_length: 7500,
-
-            cache_rounds: 3,
-            caches_count: 3,
-            caches_lock_mmap: false,
-
-            l1_enabled: true,
-            l1_cache_size: 4096 * 4,
-            l1_cache_num_items: 4096,
-        };
-        Self::new(cfg)
+        Self::from_variant(Variant::raven_coin(storage_dir))
+    }
+
+    pub fn config(&self) -> &DagConfig {
+        self.client.config()
+    }
+
+    /// Cache of the epoch, generated on first use.
+    pub fn get_cache(&self, epoch: usize) -> Result<Cache> {
+        self.client.get_cache(epoch)
     }
 
     // return mix, degest
     pub fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
-        let epoch = self.dag.config.calc_epoch(height as usize);
-        let cache = self.dag.get_cache(epoch)?;
-        let look_up = |index| {
-            generate_dataset_item_unit(cache.cache(), index, 4, self.dag.config.dataset_parents)
+        self.client.compute(hash, height, nonce)
+    }
+
+    // return mix, digest, computed from the full dataset for mining
+    pub fn compute_full(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.client.compute_full(hash, height, nonce)
+    }
+
+    /// Check that the final hash of a claimed mix reaches the target, it only takes two
+    /// keccak_f800 and no DAG access, a share passing it still has to be verified.
+    pub fn precheck(
+        &self,
+        header_hash: &[u8; 32],
+        nonce: u64,
+        claimed_mix: &[u8],
+        target: &[u8; 32],
+    ) -> bool {
+        match self
+            .client
+            .variant()
+            .final_hash(header_hash, nonce, claimed_mix)
+        {
+            Ok(digest) => digest.as_slice() <= target.as_slice(),
+            Err(_) => false,
+        }
+    }
+
+    /// Verify a share, `target` is the 256-bit big endian boundary the final hash must not exceed.
+    ///
+    /// Shares failing `precheck` are rejected as above target without recomputing the mix.
+    pub fn verify(
+        &self,
+        header_hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+        claimed_mix: &[u8],
+        target: &[u8; 32],
+    ) -> Result<Verification> {
+        self.verify_share(header_hash, nonce, claimed_mix, target, || {
+            self.compute(header_hash, height, nonce)
+        })
+    }
+
+    fn verify_share<F: FnOnce() -> Result<(Vec<u8>, Vec<u8>)>>(
+        &self,
+        header_hash: &[u8; 32],
+        nonce: u64,
+        claimed_mix: &[u8],
+        target: &[u8; 32],
+        compute: F,
+    ) -> Result<Verification> {
+        if claimed_mix.len() != 32 {
+            return Ok(Verification::MixMismatch);
+        }
+        if !self.precheck(header_hash, nonce, claimed_mix, target) {
+            return Ok(Verification::AboveTarget);
+        }
+        let (mix, digest) = compute()?;
+        Ok(Verification::check(&mix, &digest, claimed_mix, target))
+    }
+
+    /// Verify many shares on `workers` threads, the shares are grouped by epoch so each cache
+    /// is fetched once, the results are in the order of `shares`. A share whose verification
+    /// panics gets `Error::Panicked`, the others are still verified.
+    pub fn verify_batch(&self, shares: &[Share], workers: usize) -> BatchVerification {
+        let start = Instant::now();
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, share) in shares.iter().enumerate() {
+            let epoch = self.config().calc_epoch(share.height as usize);
+            groups.entry(epoch).or_default().push(i);
+        }
+        let epochs = groups.len();
+
+        let mut results: Vec<Option<Result<Verification>>> = shares.iter().map(|_| None).collect();
+        // one queue of (cache, share) over all the epochs, a worker moves on to the next
+        // epoch instead of waiting for the slowest share of the current one
+        let mut caches = vec![];
+        let mut queue: Vec<(usize, usize)> = vec![];
+        for (epoch, indexes) in groups {
+            match self.client.get_cache(epoch) {
+                Ok(cache) => {
+                    queue.extend(indexes.into_iter().map(|i| (caches.len(), i)));
+                    caches.push(cache);
+                }
+                Err(e) => {
+                    for i in indexes {
+                        results[i] = Some(Err(e.clone()));
+                    }
+                }
+            }
+        }
+
+        let next = AtomicUsize::new(0);
+        let verify = |cache: &Cache, share: &Share| {
+            self.verify_share(
+                &share.header_hash,
+                share.nonce,
+                &share.mix,
+                &share.target,
+                || {
+                    self.client
+                        .compute_with(cache, &share.header_hash, share.height, share.nonce)
+                },
+            )
+        };
+        let verified: Vec<(usize, Result<Verification>)> = thread::scope(|s| {
+            let handles: Vec<_> = (0..workers.clamp(1, queue.len().max(1)))
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut verified = vec![];
+                        loop {
+                            let k = next.fetch_add(1, Ordering::Relaxed);
+                            if k >= queue.len() {
+                                break;
+                            }
+                            let (c, i) = queue[k];
+                            let r = panic::catch_unwind(AssertUnwindSafe(|| {
+                                verify(&caches[c], &shares[i])
+                            }))
+                            .unwrap_or_else(|payload| Err(Error::Panicked(panic_message(payload))));
+                            verified.push((i, r));
+                        }
+                        verified
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                // the panics of the shares are caught, a worker itself only fails to allocate
+                .flat_map(|h| h.join().unwrap_or_default())
+                .collect()
+        });
+        for (i, r) in verified {
+            results[i] = Some(r);
+        }
+
+        BatchVerification {
+            results: results
+                .into_iter()
+                .map(|r| r.unwrap_or_else(|| Err(Error::Panicked("worker exited".into()))))
+                .collect(),
+            epochs,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// Block hash of a header in serialized byte order, the final hash of its mix hash.
+    pub fn block_hash(&self, header: &BlockHeader) -> [u8; 32] {
+        let digest = self
+            .client
+            .variant()
+            .final_hash(&header.header_hash(), header.nonce, &header.mix_digest())
+            .expect("header and mix hashes are 32 bytes");
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&digest);
+        hash.reverse();
+        hash
+    }
+
+    /// Verify the KawPow proof of work of a header against the target of its `bits`.
+    pub fn verify_block_header(&self, header: &BlockHeader) -> Result<Verification> {
+        let target = match header.target() {
+            Some(target) => target,
+            None => return Err(Error::InvalidBits(header.bits)),
         };
-        let dataset_size = self.dag.config.dataset_size(epoch);
-
-        let r = kawpow(
-            hash,
-            height as u64,
-            nonce,
-            dataset_size,
-            look_up,
-            cache.l1().unwrap(),
-        );
-        Ok(r)
+        self.verify(
+            &header.header_hash(),
+            header.height as u64,
+            header.nonce,
+            &header.mix_digest(),
+            &target,
+        )
+    }
+}
+
+impl PowAlgorithm for Client {
+    fn dag_config(&self, _height: u64) -> &DagConfig {
+        self.config()
+    }
+
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        Client::compute(self, hash, height, nonce)
+    }
+
+    fn verify(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+        mix_hash: &[u8],
+        target: &[u8; 32],
+    ) -> Result<Verification> {
+        Client::verify(self, hash, height, nonce, mix_hash, target)
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".into()
     }
 }
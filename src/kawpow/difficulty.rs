@@ -0,0 +1,90 @@
+use crate::error::{Error, Result};
+use crate::kawpow::header::BlockHeader;
+use crate::target::U256;
+
+/// Dark Gravity Wave v3 parameters.
+#[derive(Debug, Clone)]
+pub struct DgwParams {
+    pub pow_limit: U256,
+    /// returned until `past_blocks` KawPow blocks are mined
+    pub kawpow_limit: U256,
+    pub kawpow_activation_time: u32,
+    /// target seconds between blocks
+    pub target_spacing: u64,
+    pub past_blocks: usize,
+}
+
+impl DgwParams {
+    pub fn raven_coin() -> Self {
+        Self {
+            pow_limit: U256::MAX >> 20,
+            kawpow_limit: U256::MAX >> 40,
+            kawpow_activation_time: 1588788000,
+            target_spacing: 60,
+            past_blocks: 180,
+        }
+    }
+}
+
+/// Bits required for the block following the last of `ancestors` with time `time`,
+/// `ancestors` are consecutive headers ordered by height, only the last `past_blocks`
+/// are read.
+pub fn dark_gravity_wave(params: &DgwParams, ancestors: &[BlockHeader], time: u32) -> Result<u32> {
+    let past_blocks = params.past_blocks;
+    if past_blocks == 0 || params.target_spacing == 0 {
+        return Err(Error::InvalidConfig(format!("dgw params: {:?}", params)));
+    }
+    let last = match ancestors.last() {
+        Some(last) => last,
+        None => return Ok(params.pow_limit.to_compact()),
+    };
+    if (last.height as usize) < past_blocks {
+        return Ok(params.pow_limit.to_compact());
+    }
+    if ancestors.len() < past_blocks {
+        return Err(Error::InvalidInput(format!(
+            "dgw needs {} ancestors, got {}",
+            past_blocks,
+            ancestors.len()
+        )));
+    }
+
+    let mut past_target_avg = U256::ZERO;
+    let mut kawpow_blocks = 0;
+    let window = &ancestors[ancestors.len() - past_blocks..];
+    for (i, header) in window.iter().rev().enumerate() {
+        let count = i as u64 + 1;
+        let target = match U256::from_compact(header.bits) {
+            Some(target) => target,
+            None => return Err(Error::InvalidBits(header.bits)),
+        };
+        if count == 1 {
+            past_target_avg = target;
+        } else {
+            // not really an average, kept as the reference implementation computes it
+            let (sum, _) = past_target_avg.overflowing_mul_u64(count);
+            let (sum, _) = sum.overflowing_add(&target);
+            past_target_avg = sum.checked_div_rem(&U256::from(count + 1)).unwrap().0;
+        }
+        if header.time >= params.kawpow_activation_time {
+            kawpow_blocks += 1;
+        }
+    }
+
+    // switching to KawPow restarts from the KawPow limit until a full window is mined
+    if time >= params.kawpow_activation_time && kawpow_blocks != past_blocks {
+        return Ok(params.kawpow_limit.to_compact());
+    }
+
+    let target_timespan = past_blocks as u64 * params.target_spacing;
+    let actual_timespan = last.time as i64 - window[0].time as i64;
+    let actual_timespan =
+        actual_timespan.clamp(target_timespan as i64 / 3, target_timespan as i64 * 3) as u64;
+
+    let (target, _) = past_target_avg.overflowing_mul_u64(actual_timespan);
+    let target = target
+        .checked_div_rem(&U256::from(target_timespan))
+        .unwrap()
+        .0;
+    Ok(target.min(params.pow_limit).to_compact())
+}
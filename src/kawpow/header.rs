@@ -0,0 +1,85 @@
+use crate::error::{Error, Result};
+use crate::internal::crypto::sha256::sha256d;
+use crate::target::U256;
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Size of the serialized KawPow header.
+pub const HEADER_SIZE: usize = 120;
+/// Size of the header part hashed into the KawPow header hash, without nonce and mix hash.
+pub const HEADER_HASH_INPUT_SIZE: usize = 80;
+
+/// Ravencoin block header since the KawPow activation.
+///
+/// The hashes are kept in their serialized (little endian) byte order, the order the
+/// node prints them in is the reverse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub prev_hash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub height: u32,
+    pub nonce: u64,
+    pub mix_hash: [u8; 32],
+}
+
+impl BlockHeader {
+    pub fn from_bytes(b: &[u8]) -> Result<Self> {
+        if b.len() != HEADER_SIZE {
+            return Err(Error::InvalidInput(format!(
+                "invalid header size: {}",
+                b.len()
+            )));
+        }
+        let mut prev_hash = [0u8; 32];
+        prev_hash.copy_from_slice(&b[4..36]);
+        let mut merkle_root = [0u8; 32];
+        merkle_root.copy_from_slice(&b[36..68]);
+        let mut mix_hash = [0u8; 32];
+        mix_hash.copy_from_slice(&b[88..120]);
+        Ok(Self {
+            version: LittleEndian::read_i32(&b[0..4]),
+            prev_hash,
+            merkle_root,
+            time: LittleEndian::read_u32(&b[68..72]),
+            bits: LittleEndian::read_u32(&b[72..76]),
+            height: LittleEndian::read_u32(&b[76..80]),
+            nonce: LittleEndian::read_u64(&b[80..88]),
+            mix_hash,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut b = vec![0u8; HEADER_SIZE];
+        LittleEndian::write_i32(&mut b[0..4], self.version);
+        b[4..36].copy_from_slice(&self.prev_hash);
+        b[36..68].copy_from_slice(&self.merkle_root);
+        LittleEndian::write_u32(&mut b[68..72], self.time);
+        LittleEndian::write_u32(&mut b[72..76], self.bits);
+        LittleEndian::write_u32(&mut b[76..80], self.height);
+        LittleEndian::write_u64(&mut b[80..88], self.nonce);
+        b[88..120].copy_from_slice(&self.mix_hash);
+        b
+    }
+
+    /// The KawPow header hash, SHA256d of the header without nonce and mix hash, in the
+    /// byte order `Client::compute` takes it.
+    pub fn header_hash(&self) -> [u8; 32] {
+        let mut hash = sha256d(&self.to_bytes()[..HEADER_HASH_INPUT_SIZE]);
+        hash.reverse();
+        hash
+    }
+
+    /// The mix hash in the byte order `Client::compute` returns it.
+    pub fn mix_digest(&self) -> [u8; 32] {
+        let mut mix = self.mix_hash;
+        mix.reverse();
+        mix
+    }
+
+    /// The big endian target encoded by `bits`, None if it is negative or overflows.
+    pub fn target(&self) -> Option<[u8; 32]> {
+        U256::from_compact(self.bits).map(|target| target.to_be_bytes())
+    }
+}
@@ -1,2 +1,4 @@
 pub mod client;
-pub(crate) mod lookup;
+pub mod difficulty;
+pub mod header;
+pub mod validator;
@@ -0,0 +1,242 @@
+use crate::algorithm::Verification;
+use crate::error::{Error, Result};
+use crate::kawpow::client::Client;
+use crate::kawpow::difficulty::{dark_gravity_wave, DgwParams};
+use crate::kawpow::header::{BlockHeader, HEADER_SIZE};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+
+/// Number of blocks of the median time past.
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// Why a header was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidReason {
+    UnexpectedHeight {
+        /// `None` when the parent is at the last height a `u32` holds
+        expected: Option<u32>,
+        got: u32,
+    },
+    PrevHashMismatch,
+    /// not after the median time of the previous blocks
+    TimeTooOld {
+        median_time: u32,
+    },
+    TimeTooNew {
+        max_time: u32,
+    },
+    InvalidBits,
+    UnexpectedBits {
+        expected: u32,
+        got: u32,
+    },
+    InvalidPow(Verification),
+}
+
+/// The first invalid header of a chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidHeader {
+    /// position in the validated stream
+    pub index: usize,
+    pub height: u32,
+    pub reason: InvalidReason,
+}
+
+/// Validate a stream of consecutive KawPow headers: linkage, timestamps, DGW difficulty and
+/// proof of work.
+///
+/// The difficulty of a header is only checked once enough ancestors are known, pass the
+/// headers preceding the stream with `with_ancestors` to check it from the first header.
+pub struct ChainValidator<'a> {
+    client: &'a Client,
+    params: DgwParams,
+    /// latest validated (or trusted) headers, oldest first
+    ancestors: VecDeque<BlockHeader>,
+    /// headers with a later time are rejected
+    max_time: Option<u32>,
+    epoch: Option<usize>,
+}
+
+impl<'a> ChainValidator<'a> {
+    pub fn new(client: &'a Client, params: DgwParams) -> Self {
+        Self {
+            client,
+            params,
+            ancestors: VecDeque::new(),
+            max_time: None,
+            epoch: None,
+        }
+    }
+
+    /// Trusted headers preceding the validated ones.
+    pub fn with_ancestors(mut self, headers: impl IntoIterator<Item = BlockHeader>) -> Self {
+        for header in headers {
+            self.push(header);
+        }
+        self
+    }
+
+    /// Reject headers with a time after `max_time`, e.g. now plus the allowed drift.
+    pub fn with_max_time(mut self, max_time: u32) -> Self {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    fn push(&mut self, header: BlockHeader) {
+        self.ancestors.push_back(header);
+        let keep = self.params.past_blocks.max(MEDIAN_TIME_SPAN);
+        while self.ancestors.len() > keep {
+            self.ancestors.pop_front();
+        }
+    }
+
+    fn median_time(&self) -> Option<u32> {
+        let mut times: Vec<u32> = self
+            .ancestors
+            .iter()
+            .rev()
+            .take(MEDIAN_TIME_SPAN)
+            .map(|header| header.time)
+            .collect();
+        times.sort_unstable();
+        times.get(times.len() / 2).copied()
+    }
+
+    fn expected_bits(&mut self, header: &BlockHeader) -> Result<Option<u32>> {
+        let known = self.ancestors.len();
+        let height = header.height as usize;
+        // DGW returns the pow limit without a full window
+        if known < self.params.past_blocks && known < height {
+            return Ok(None);
+        }
+        let ancestors = self.ancestors.make_contiguous();
+        dark_gravity_wave(&self.params, ancestors, header.time).map(Some)
+    }
+
+    /// Validate the header following the latest one, an error is returned when it could
+    /// not be checked, e.g. the cache cannot be generated.
+    pub fn validate(&mut self, header: &BlockHeader) -> Result<Option<InvalidReason>> {
+        if let Some(reason) = self.check(header)? {
+            return Ok(Some(reason));
+        }
+        self.push(header.clone());
+        Ok(None)
+    }
+
+    fn check(&mut self, header: &BlockHeader) -> Result<Option<InvalidReason>> {
+        if let Some(parent) = self.ancestors.back() {
+            let expected = parent.height.checked_add(1);
+            if expected != Some(header.height) {
+                return Ok(Some(InvalidReason::UnexpectedHeight {
+                    expected,
+                    got: header.height,
+                }));
+            }
+            if header.prev_hash != self.client.block_hash(parent) {
+                return Ok(Some(InvalidReason::PrevHashMismatch));
+            }
+        }
+
+        if let Some(median_time) = self.median_time() {
+            if header.time <= median_time {
+                return Ok(Some(InvalidReason::TimeTooOld { median_time }));
+            }
+        }
+        if let Some(max_time) = self.max_time {
+            if header.time > max_time {
+                return Ok(Some(InvalidReason::TimeTooNew { max_time }));
+            }
+        }
+
+        if header.target().is_none() {
+            return Ok(Some(InvalidReason::InvalidBits));
+        }
+        if let Some(expected) = self.expected_bits(header)? {
+            if header.bits != expected {
+                return Ok(Some(InvalidReason::UnexpectedBits {
+                    expected,
+                    got: header.bits,
+                }));
+            }
+        }
+
+        // entering an epoch generates its cache, the next one is prepared in the background
+        let epoch = self.client.config().calc_epoch(header.height as usize);
+        if self.epoch != Some(epoch) {
+            self.client.get_cache(epoch)?;
+            self.epoch = Some(epoch);
+        }
+        match self.client.verify_block_header(header)? {
+            Verification::Valid => Ok(None),
+            verification => Ok(Some(InvalidReason::InvalidPow(verification))),
+        }
+    }
+
+    /// Validate the headers in order and return the first invalid one.
+    pub fn validate_all<I: IntoIterator<Item = BlockHeader>>(
+        &mut self,
+        headers: I,
+    ) -> Result<Option<InvalidHeader>> {
+        self.validate_stream(headers.into_iter().map(Ok))
+    }
+
+    /// Validate the headers read from a file of consecutive serialized headers.
+    pub fn validate_file<P: AsRef<Path>>(&mut self, path: P) -> Result<Option<InvalidHeader>> {
+        let reader = BufReader::new(File::open(path)?);
+        self.validate_stream(HeaderReader::new(reader))
+    }
+
+    fn validate_stream<I: Iterator<Item = Result<BlockHeader>>>(
+        &mut self,
+        headers: I,
+    ) -> Result<Option<InvalidHeader>> {
+        for (index, header) in headers.enumerate() {
+            let header = header?;
+            if let Some(reason) = self.validate(&header)? {
+                return Ok(Some(InvalidHeader {
+                    index,
+                    height: header.height,
+                    reason,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Iterate the headers of a reader of consecutive serialized headers.
+pub struct HeaderReader<R> {
+    reader: R,
+}
+
+impl<R: Read> HeaderReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for HeaderReader<R> {
+    type Item = Result<BlockHeader>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = [0u8; HEADER_SIZE];
+        let mut read = 0;
+        while read < HEADER_SIZE {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) if read == 0 => return None,
+                Ok(0) => {
+                    return Some(Err(Error::InvalidInput(format!(
+                        "truncated header of {} bytes",
+                        read
+                    ))))
+                }
+                Ok(n) => read += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+        Some(BlockHeader::from_bytes(&buf))
+    }
+}
@@ -1,7 +1,13 @@
+pub mod algorithm;
 pub mod constant;
+pub mod error;
+pub mod etchash;
+pub mod ethash;
+pub mod evrprogpow;
 pub mod internal;
 pub mod kawpow;
-#[macro_use]
-extern crate anyhow;
+pub mod meowpow;
+pub mod target;
+pub mod ubqhash;
 #[macro_use]
 extern crate log;
@@ -0,0 +1,12 @@
+use crate::internal::progpow;
+use crate::internal::progpow::variant::Variant;
+use std::path::PathBuf;
+
+/// MeowPow is the ProgPoW client of the Meowcoin variant.
+pub type Client = progpow::client::Client;
+
+impl Client {
+    pub fn new_meowcoin(storage_dir: PathBuf) -> Self {
+        Self::new(Variant::meowcoin(storage_dir))
+    }
+}
@@ -0,0 +1,359 @@
+use std::cmp::Ordering;
+use std::ops::{Shl, Shr};
+
+/// Compact bits of the Bitcoin style difficulty 1 target.
+pub const DIFF1_BITS: u32 = 0x1d00ffff;
+
+/// 256-bit unsigned integer for targets and difficulties, little endian 64-bit limbs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    pub const ZERO: U256 = U256([0; 4]);
+    pub const ONE: U256 = U256([1, 0, 0, 0]);
+    pub const MAX: U256 = U256([u64::MAX; 4]);
+
+    pub fn from_be_bytes(b: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&b[start..start + 8]);
+            *limb = u64::from_be_bytes(word);
+        }
+        U256(limbs)
+    }
+
+    /// Big endian bytes of at most 32 bytes, e.g. the digest returned by `Client::compute`.
+    pub fn from_be_slice(b: &[u8]) -> Option<Self> {
+        if b.len() > 32 {
+            return None;
+        }
+        let mut bytes = [0u8; 32];
+        bytes[32 - b.len()..].copy_from_slice(b);
+        Some(Self::from_be_bytes(&bytes))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut b = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            b[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        b
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub fn low_u64(&self) -> u64 {
+        self.0[0]
+    }
+
+    /// Number of significant bits.
+    pub fn bits(&self) -> u32 {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return 64 * i as u32 + 64 - self.0[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    fn bit(&self, i: u32) -> bool {
+        self.0[(i / 64) as usize] >> (i % 64) & 1 == 1
+    }
+
+    fn set_bit(&mut self, i: u32) {
+        self.0[(i / 64) as usize] |= 1 << (i % 64);
+    }
+
+    pub fn overflowing_add(&self, rhs: &U256) -> (U256, bool) {
+        let mut out = [0u64; 4];
+        let mut carry = false;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (U256(out), carry)
+    }
+
+    pub fn wrapping_sub(&self, rhs: &U256) -> U256 {
+        let mut out = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        U256(out)
+    }
+
+    pub fn overflowing_mul_u64(&self, rhs: u64) -> (U256, bool) {
+        let mut out = [0u64; 4];
+        let mut carry = 0u128;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let product = self.0[i] as u128 * rhs as u128 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        (U256(out), carry != 0)
+    }
+
+    /// Quotient and remainder, None when dividing by zero.
+    pub fn checked_div_rem(&self, rhs: &U256) -> Option<(U256, U256)> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let mut quotient = U256::ZERO;
+        let mut remainder = U256::ZERO;
+        for i in (0..self.bits()).rev() {
+            let carry = remainder.bit(255);
+            remainder = remainder << 1;
+            if self.bit(i) {
+                remainder.0[0] |= 1;
+            }
+            if carry || remainder >= *rhs {
+                remainder = remainder.wrapping_sub(rhs);
+                quotient.set_bit(i);
+            }
+        }
+        Some((quotient, remainder))
+    }
+
+    /// Decode Bitcoin style compact bits, None if the value is negative or overflows.
+    pub fn from_compact(bits: u32) -> Option<Self> {
+        let size = bits >> 24;
+        let word = bits & 0x007fffff;
+        if word == 0 {
+            return Some(U256::ZERO);
+        }
+        if bits & 0x00800000 != 0 {
+            return None;
+        }
+        if size > 34 || (word > 0xff && size > 33) || (word > 0xffff && size > 32) {
+            return None;
+        }
+        if size <= 3 {
+            Some(U256::from(word as u64 >> (8 * (3 - size))))
+        } else {
+            Some(U256::from(word as u64) << (8 * (size - 3)))
+        }
+    }
+
+    /// Encode to Bitcoin style compact bits.
+    pub fn to_compact(&self) -> u32 {
+        let mut size = self.bits().div_ceil(8);
+        let mut compact = if size <= 3 {
+            (self.low_u64() << (8 * (3 - size))) as u32
+        } else {
+            (*self >> (8 * (size - 3))).low_u64() as u32
+        };
+        // the sign bit is set, use one more byte
+        if compact & 0x00800000 != 0 {
+            compact >>= 8;
+            size += 1;
+        }
+        compact | (size << 24)
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0
+            .iter()
+            .rev()
+            .fold(0.0, |acc, limb| acc * 18446744073709551616.0 + *limb as f64)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(v: u64) -> Self {
+        U256([v, 0, 0, 0])
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = U256;
+
+    fn shl(self, shift: u32) -> U256 {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+        let limbs = (shift / 64) as usize;
+        let bits = shift % 64;
+        let mut out = [0u64; 4];
+        for (i, limb) in out.iter_mut().enumerate().skip(limbs) {
+            *limb = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                *limb |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        U256(out)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = U256;
+
+    fn shr(self, shift: u32) -> U256 {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+        let limbs = (shift / 64) as usize;
+        let bits = shift % 64;
+        let mut out = [0u64; 4];
+        for (i, limb) in out.iter_mut().take(4 - limbs).enumerate() {
+            *limb = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < 4 {
+                *limb |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        U256(out)
+    }
+}
+
+/// The Bitcoin style difficulty 1 target used by Raven pools.
+pub fn diff1_target() -> U256 {
+    U256::from_compact(DIFF1_BITS).unwrap()
+}
+
+/// Bitcoin style target of a (pool) difficulty, diff1 / difficulty.
+pub fn target_from_difficulty(difficulty: f64) -> U256 {
+    // difficulty in 32-bit fixed point, diff1 << 32 still fits in 256 bits
+    let divisor = difficulty * 4294967296.0;
+    if divisor.is_nan() || divisor < 1.0 {
+        return U256::MAX;
+    }
+    let divisor = if divisor >= u128::MAX as f64 {
+        u128::MAX
+    } else {
+        divisor as u128
+    };
+    let divisor = U256([divisor as u64, (divisor >> 64) as u64, 0, 0]);
+    let (target, _) = (diff1_target() << 32).checked_div_rem(&divisor).unwrap();
+    target
+}
+
+/// Bitcoin style difficulty of a target, diff1 / target.
+pub fn difficulty_from_target(target: &U256) -> f64 {
+    if target.is_zero() {
+        return f64::INFINITY;
+    }
+    diff1_target().as_f64() / target.as_f64()
+}
+
+/// Bitcoin style difficulty a big endian final hash reaches, None if it is longer than 32 bytes.
+pub fn hash_difficulty(hash: &[u8]) -> Option<f64> {
+    U256::from_be_slice(hash).map(|hash| difficulty_from_target(&hash))
+}
+
+/// Ethash boundary of a difficulty, 2^256 / difficulty capped to `U256::MAX`.
+pub fn ethash_boundary(difficulty: &U256) -> U256 {
+    if *difficulty <= U256::ONE {
+        return U256::MAX;
+    }
+    let (quotient, remainder) = U256::MAX.checked_div_rem(difficulty).unwrap();
+    // 2^256 = MAX + 1
+    if remainder.overflowing_add(&U256::ONE).0 == *difficulty {
+        quotient.overflowing_add(&U256::ONE).0
+    } else {
+        quotient
+    }
+}
+
+/// Ethash difficulty a big endian final hash reaches, 2^256 / hash, None if it is longer than
+/// 32 bytes.
+pub fn ethash_difficulty(hash: &[u8]) -> Option<U256> {
+    U256::from_be_slice(hash).map(|hash| ethash_boundary(&hash))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(v: &U256) -> String {
+        hex::encode(v.to_be_bytes())
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(
+            hex(&U256::from_compact(0x1d00ffff).unwrap()),
+            "00000000ffff0000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            hex(&U256::from_compact(0x1b0404cb).unwrap()),
+            "00000000000404cb000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(U256::from_compact(0x01003456), Some(U256::ZERO));
+        assert_eq!(U256::from_compact(0x02123456), Some(U256::from(0x1234)));
+        assert_eq!(U256::from_compact(0x04923456), None);
+        assert_eq!(U256::from_compact(0xff123456), None);
+
+        for bits in [0x1d00ffff, 0x1b0404cb, 0x207fffff, 0x1e00ffff, 0x03123456] {
+            assert_eq!(U256::from_compact(bits).unwrap().to_compact(), bits);
+        }
+        assert_eq!(U256::from(0x80).to_compact(), 0x02008000);
+        assert_eq!(U256::ZERO.to_compact(), 0);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let (q, r) = U256::MAX.checked_div_rem(&U256::from(3)).unwrap();
+        assert_eq!(q, U256([0x5555555555555555; 4]));
+        assert_eq!(r, U256::ZERO);
+        let (q, r) = U256::from(100).checked_div_rem(&U256::from(7)).unwrap();
+        assert_eq!((q, r), (U256::from(14), U256::from(2)));
+        assert_eq!(U256::ONE.checked_div_rem(&U256::ZERO), None);
+        assert_eq!(
+            (U256::ONE << 200).overflowing_mul_u64(1 << 20),
+            (U256::ONE << 220, false)
+        );
+        assert_eq!(
+            (U256::ONE << 250).overflowing_mul_u64(1 << 6),
+            (U256::ZERO, true)
+        );
+        assert!(U256::ONE << 255 > U256::MAX >> 1);
+    }
+
+    #[test]
+    fn test_bitcoin_difficulty() {
+        assert_eq!(target_from_difficulty(1.0), diff1_target());
+        assert_eq!(target_from_difficulty(2.0), diff1_target() >> 1);
+        assert_eq!(target_from_difficulty(0.5), diff1_target() << 1);
+        assert_eq!(target_from_difficulty(0.0), U256::MAX);
+        assert_eq!(difficulty_from_target(&diff1_target()), 1.0);
+        assert_eq!(difficulty_from_target(&(diff1_target() >> 4)), 16.0);
+        assert_eq!(hash_difficulty(&diff1_target().to_be_bytes()), Some(1.0));
+        assert_eq!(hash_difficulty(&[0; 33]), None);
+    }
+
+    #[test]
+    fn test_ethash_difficulty() {
+        assert_eq!(ethash_boundary(&U256::ONE), U256::MAX);
+        assert_eq!(ethash_boundary(&U256::from(2)), U256::ONE << 255);
+        assert_eq!(
+            ethash_boundary(&U256::from(3)),
+            U256([0x5555555555555555; 4])
+        );
+        assert_eq!(
+            ethash_difficulty(&(U256::ONE << 224).to_be_bytes()),
+            Some(U256::from(1 << 32))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::algorithm::{PowAlgorithm, Verification};
+use crate::error::Result;
+use crate::ethash;
+use crate::ethash::header::Header;
+use crate::internal::dag::config::{CacheHasher, DagConfig, LookupTable};
+use crate::internal::dag::lookup::{CACHE_SIZES, DATASET_SIZES};
+use std::path::PathBuf;
+
+/// UIP-1 epoch, Ubiq switched from Ethash to Ubqhash at this epoch.
+pub const UIP1_EPOCH: usize = 22;
+
+/// Ubqhash is Ethash with the cache generated by SHA3-512 instead of Keccak-512,
+/// the dataset items and hashimoto are left unchanged.
+pub struct Client {
+    activation_height: u64,
+    /// before the activation
+    legacy: ethash::client::Client,
+    /// from the activation on
+    ubqhash: ethash::client::Client,
+}
+
+impl Client {
+    pub fn new(cfg: DagConfig, activation_height: u64) -> Self {
+        let mut ubqhash_cfg = cfg.clone();
+        ubqhash_cfg.name = format!("{}-UBQHASH", cfg.name);
+        ubqhash_cfg.cache_hasher = CacheHasher::Sha3_512;
+        Self {
+            activation_height,
+            legacy: ethash::client::Client::new(cfg),
+            ubqhash: ethash::client::Client::new(ubqhash_cfg),
+        }
+    }
+
+    pub fn new_ubiq(storage_dir: PathBuf) -> Self {
+        let cfg = DagConfig {
+            name: "UBQ".into(),
+            revision: 23,
+            storage_dir,
+
+            dataset_init_bytes: 1 << 30,
+            dataset_growth_bytes: 1 << 23,
+            cache_init_bytes: 1 << 24,
+            cache_growth_bytes: 1 << 17,
+
+            cache_sizes: LookupTable::new(CACHE_SIZES.to_vec()),
+            dataset_sizes: LookupTable::new(DATASET_SIZES.to_vec()),
+
+            mix_bytes: 128,
+            dataset_parents: 256,
+            epoch_length: 30000,
+            seed_epoch_length: 30000,
+
+            cache_rounds: 3,
+            cache_hasher: CacheHasher::Keccak512,
+            caches_count: 3,
+            caches_on_disk: 3,
+            caches_lock_mmap: false,
+
+            l1_enabled: false,
+            l1_cache_size: 0,
+            l1_cache_num_items: 0,
+        };
+        let activation_height = (UIP1_EPOCH * cfg.epoch_length) as u64;
+        Self::new(cfg, activation_height)
+    }
+
+    fn client(&self, height: u64) -> &ethash::client::Client {
+        if height >= self.activation_height {
+            &self.ubqhash
+        } else {
+            &self.legacy
+        }
+    }
+
+    pub fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    // return mix, digest
+    pub fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.client(height).compute(hash, height, nonce)
+    }
+
+    /// Verify the seal of a header against the boundary of its difficulty.
+    pub fn verify_header(&self, header: &Header) -> Result<Verification> {
+        self.client(header.number).verify_header(header)
+    }
+
+    // return mix, digest, computed from the full dataset
+    pub fn compute_full(
+        &self,
+        hash: &[u8; 32],
+        height: u64,
+        nonce: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.client(height).compute_full(hash, height, nonce)
+    }
+}
+
+impl PowAlgorithm for Client {
+    fn dag_config(&self, height: u64) -> &DagConfig {
+        self.client(height).config()
+    }
+
+    fn compute(&self, hash: &[u8; 32], height: u64, nonce: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        Client::compute(self, hash, height, nonce)
+    }
+}
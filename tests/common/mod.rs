@@ -0,0 +1,35 @@
+use powkit::internal::dag::config::{CacheHasher, DagConfig, LookupTable};
+use std::path::PathBuf;
+
+/// A DAG of a few KiB with epochs of 4 blocks and the L1 cache, stored in /tmp under
+/// `name`, tests use distinct names so their files don't collide.
+pub fn tiny_config(name: &str) -> DagConfig {
+    DagConfig {
+        name: name.into(),
+        revision: 23,
+        storage_dir: PathBuf::from("/tmp"),
+
+        dataset_init_bytes: 1 << 16,
+        dataset_growth_bytes: 1 << 10,
+        cache_init_bytes: 1 << 14,
+        cache_growth_bytes: 1 << 8,
+
+        dataset_sizes: LookupTable::default(),
+        cache_sizes: LookupTable::default(),
+
+        mix_bytes: 128,
+        dataset_parents: 64,
+        epoch_length: 4,
+        seed_epoch_length: 4,
+
+        cache_rounds: 3,
+        cache_hasher: CacheHasher::Keccak512,
+        caches_count: 3,
+        caches_on_disk: 3,
+        caches_lock_mmap: false,
+
+        l1_enabled: true,
+        l1_cache_size: 4096 * 4,
+        l1_cache_num_items: 4096,
+    }
+}
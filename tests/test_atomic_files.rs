@@ -0,0 +1,100 @@
+use powkit::internal::dag::cache::{generate_cache, generate_dataset};
+use powkit::internal::dag::config::{DagConfig, DataKind};
+use powkit::internal::dag::file_header::{config_hash, HEADER_SIZE};
+use powkit::kawpow::client::Client;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use common::tiny_config;
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}", path.display(), suffix))
+}
+
+#[test]
+fn test_generated_files_are_renamed() {
+    let cfg = tiny_config("RVNATOMIC");
+    let path = cfg.file_path(0, DataKind::Cache);
+    // a crash of the previous layout left a full size file without the finished flag
+    fs::write(&path, vec![0; cfg.cache_size(0) + 1]).unwrap();
+
+    Client::new(cfg.clone()).compute(&[0; 32], 0, 0).unwrap();
+    let data = fs::read(&path).unwrap();
+    assert_eq!(data.last(), Some(&1));
+    assert!(!with_suffix(&path, ".tmp").exists());
+    assert!(!with_suffix(&cfg.file_path(0, DataKind::L1), ".tmp").exists());
+}
+
+#[test]
+fn test_dataset_resumes_from_checkpoint() {
+    let cfg = tiny_config("RVNRESUME");
+    let size = cfg.dataset_size(0);
+    let path = cfg.file_path(0, DataKind::Dataset);
+    let tmp_path = with_suffix(&path, ".tmp");
+    let progress_path = with_suffix(&path, ".progress");
+    let _ = fs::remove_file(&path);
+
+    // an interrupted generation had written 100 rows, marked so they can be told apart
+    let done = 100;
+    let mut tmp = vec![0; HEADER_SIZE + size + 1];
+    tmp[HEADER_SIZE..HEADER_SIZE + done * 64].fill(0xaa);
+    fs::write(&tmp_path, &tmp).unwrap();
+    fs::write(&progress_path, progress(&cfg, done)).unwrap();
+
+    Client::new(cfg.clone())
+        .compute_full(&[0; 32], 0, 0)
+        .unwrap();
+    assert!(!tmp_path.exists());
+    assert!(!progress_path.exists());
+
+    let mut cache = vec![0; cfg.cache_size(0) + 1];
+    generate_cache(
+        &mut cache,
+        &cfg.seed_for_epoch(0).unwrap(),
+        cfg.cache_rounds,
+    )
+    .unwrap();
+    let mut expected = vec![0; size + 1];
+    generate_dataset(
+        &mut expected,
+        &cache[..cache.len() - 1],
+        cfg.dataset_parents,
+    )
+    .unwrap();
+
+    let data = fs::read(&path).unwrap();
+    let data = &data[HEADER_SIZE..];
+    assert!(data[..done * 64].iter().all(|b| *b == 0xaa));
+    assert_eq!(data[done * 64..], expected[done * 64..]);
+
+    // a progress without its data file starts over
+    fs::remove_file(&path).unwrap();
+    fs::write(&progress_path, progress(&cfg, done)).unwrap();
+    Client::new(cfg.clone())
+        .compute_full(&[0; 32], 0, 0)
+        .unwrap();
+    assert_eq!(fs::read(&path).unwrap()[HEADER_SIZE..], expected);
+
+    // so does the progress of another config under the same name
+    fs::remove_file(&path).unwrap();
+    fs::write(&tmp_path, &tmp).unwrap();
+    let other = DagConfig {
+        dataset_parents: 32,
+        ..cfg.clone()
+    };
+    fs::write(&progress_path, progress(&other, done)).unwrap();
+    Client::new(cfg).compute_full(&[0; 32], 0, 0).unwrap();
+    assert_eq!(fs::read(&path).unwrap()[HEADER_SIZE..], expected);
+}
+
+/// Progress of an interrupted dataset generation of epoch 0, see `DataFile::read_progress`.
+fn progress(cfg: &DagConfig, row: usize) -> Vec<u8> {
+    [
+        &(row as u64).to_le_bytes()[..],
+        &cfg.seed_for_epoch(0).unwrap(),
+        &config_hash(cfg, 0, DataKind::Dataset),
+    ]
+    .concat()
+}
@@ -0,0 +1,89 @@
+use powkit::algorithm::PowAlgorithm;
+use powkit::etchash::client::{Client, ECIP1099_MAINNET_HEIGHT, ECIP1099_MORDOR_HEIGHT};
+use powkit::internal::dag::cache::generate_cache;
+use powkit::internal::ethash::hashimoto_light;
+use std::path::PathBuf;
+
+#[test]
+fn test_etchash_epoch() {
+    let client = Client::new_ethereum_classic(PathBuf::from("/tmp"));
+    let ethash = powkit::ethash::client::Client::new_ethereum(PathBuf::from("/tmp"));
+
+    let before = ECIP1099_MAINNET_HEIGHT - 1;
+    assert_eq!(client.epoch(before), 389);
+    assert_eq!(client.epoch_length(before), 30000);
+    assert_eq!(
+        client.seed_hash(before).unwrap(),
+        ethash.seed_hash(before).unwrap()
+    );
+    assert_eq!(
+        client.cache_size(before).unwrap(),
+        ethash.config().cache_size(389)
+    );
+    assert_eq!(
+        client.dataset_size(before).unwrap(),
+        ethash.config().dataset_size(389)
+    );
+
+    // the transition epoch 195 uses the seed of the legacy epoch 390 and the sizes of 195
+    let after = ECIP1099_MAINNET_HEIGHT;
+    assert_eq!(client.epoch(after), 195);
+    assert_eq!(client.epoch_length(after), 60000);
+    assert_eq!(
+        client.seed_hash(after).unwrap(),
+        ethash.seed_hash(after).unwrap()
+    );
+    assert_eq!(
+        client.cache_size(after).unwrap(),
+        ethash.config().cache_size(195)
+    );
+    assert_eq!(
+        client.dataset_size(after).unwrap(),
+        ethash.config().dataset_size(195)
+    );
+    assert_eq!(client.epoch(after + 59999), 195);
+    assert_eq!(client.epoch(after + 60000), 196);
+
+    let mordor = Client::new_mordor(PathBuf::from("/tmp"));
+    assert_eq!(mordor.epoch(ECIP1099_MORDOR_HEIGHT - 1), 83);
+    assert_eq!(mordor.epoch(ECIP1099_MORDOR_HEIGHT), 42);
+}
+
+#[test]
+fn test_compute_etchash() {
+    let client = Client::new_ethereum_classic(PathBuf::from("/tmp"));
+
+    // before the fork etchash is ethash
+    let hash: [u8; 32] =
+        hex::decode("2a8de2adf89af77358250bf908bf04ba94a6e8c3ba87775564a41d269a05e4ce")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let (mix, digest) = client.compute(&hash, 0, 0x4242424242424242).unwrap();
+    assert_eq!(
+        hex::encode(mix),
+        "58f759ede17a706c93f13030328bcea40c1d1341fb26f2facd21ceb0dae57017"
+    );
+    assert_eq!(
+        hex::encode(digest),
+        "dd47fd2d98db51078356852d7c4014e6a5d6c387c35f40e2875b74a256ed7906"
+    );
+
+    // after the fork the cache is built from the doubled epoch seed
+    // TODO: this only checks the client against hashimoto over the same seed and sizes,
+    // pin the header hash, nonce, mix and digest of a mined ETC block from 11,700,000 on
+    let height = ECIP1099_MAINNET_HEIGHT;
+    let nonce = 0x8ec9f1f2a7d6a3b1;
+    let cache_size = client.cache_size(height).unwrap();
+    let mut cache = vec![0; cache_size + 1];
+    generate_cache(&mut cache, &client.seed_hash(height).unwrap(), 3).unwrap();
+    let expected = hashimoto_light(
+        &hash,
+        nonce,
+        client.dataset_size(height).unwrap(),
+        &cache[..cache_size],
+        256,
+    );
+    let (mix, digest) = client.compute(&hash, height, nonce).unwrap();
+    assert_eq!((mix, digest), expected);
+}
@@ -0,0 +1,51 @@
+use powkit::ethash::client::Client;
+use powkit::internal::dag::cache::{generate_cache, generate_dataset};
+use powkit::internal::ethash::{hashimoto_full, hashimoto_light};
+use std::path::PathBuf;
+
+#[test]
+fn test_hashimoto() {
+    // Create the verification cache and mining dataset
+    let mut cache = vec![0; 1024 + 1];
+    generate_cache(&mut cache, &[0; 32], 3).unwrap();
+    let cache = &cache[..1024];
+
+    let mut dataset = vec![0; 32 * 1024 + 1];
+    generate_dataset(&mut dataset, cache, 256).unwrap();
+    let dataset = &dataset[..32 * 1024];
+
+    let hash =
+        hex::decode("c9149cc0386e689d789a1c2f3d5d169a61a6218ed30e74414dc736e442ef3d1f").unwrap();
+    let nonce = 0;
+    let want_mix = "e4073cffaef931d37117cefd9afd27ea0f1cad6a981dd2605c4a1ac97c519800";
+    let want_digest = "d3539235ee2e6f8db665c0a72169f55b7f6c605712330b778ec3944f0eb5a557";
+
+    let (mix, digest) = hashimoto_light(&hash, nonce, 32 * 1024, cache, 256);
+    assert_eq!(hex::encode(mix), want_mix);
+    assert_eq!(hex::encode(digest), want_digest);
+
+    let (mix, digest) = hashimoto_full(&hash, nonce, dataset);
+    assert_eq!(hex::encode(mix), want_mix);
+    assert_eq!(hex::encode(digest), want_digest);
+}
+
+#[test]
+fn test_compute_ethereum() {
+    // https://github.com/ethereum/tests/blob/develop/PoWTests/ethash_tests.json
+    let client = Client::new_ethereum(PathBuf::from("/tmp"));
+    let hash: [u8; 32] =
+        hex::decode("2a8de2adf89af77358250bf908bf04ba94a6e8c3ba87775564a41d269a05e4ce")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x4242424242424242;
+    let (mix, digest) = client.compute(&hash, 0, nonce).unwrap();
+    assert_eq!(
+        hex::encode(mix),
+        "58f759ede17a706c93f13030328bcea40c1d1341fb26f2facd21ceb0dae57017"
+    );
+    assert_eq!(
+        hex::encode(digest),
+        "dd47fd2d98db51078356852d7c4014e6a5d6c387c35f40e2875b74a256ed7906"
+    );
+}
@@ -0,0 +1,38 @@
+use powkit::evrprogpow::client::Client;
+use std::path::PathBuf;
+
+#[test]
+fn test_evrmore_dag_schedule() {
+    let client = Client::new_evrmore(PathBuf::from("/tmp"));
+    let cfg = client.config();
+    assert_eq!(cfg.calc_epoch(11999), 0);
+    assert_eq!(cfg.calc_epoch(12000), 1);
+    assert_eq!(cfg.cache_size(0), 16776896);
+    assert!(cfg.dataset_size(0) < 3 << 30);
+    assert!(cfg.dataset_size(0) > (3 << 30) - (1 << 20));
+}
+
+#[test]
+fn test_compute_evrmore() {
+    let client = Client::new_evrmore(PathBuf::from("/tmp"));
+    let kawpow = powkit::kawpow::client::Client::new_raven_coin(PathBuf::from("/tmp"));
+    let hash: [u8; 32] =
+        hex::decode("9e7248f20914913a73d80a70174c331b1d34f260535ac3631d770e656b5dd922")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x00000000076e482e;
+
+    // TODO: these only check the schedule and the difference from KawPow, pin the hash,
+    // height, nonce, mix and final hash of a mined Evrmore block
+    // the program period is the KawPow one
+    let (mix_0, digest_0) = client.compute(&hash, 0, nonce).unwrap();
+    let (mix_2, digest_2) = client.compute(&hash, 2, nonce).unwrap();
+    assert_eq!(mix_0, mix_2);
+    assert_eq!(digest_0, digest_2);
+
+    // but the padding and the dataset parents differ
+    let (kawpow_mix, kawpow_digest) = kawpow.compute(&hash, 0, nonce).unwrap();
+    assert_ne!(mix_0, kawpow_mix);
+    assert_ne!(digest_0, kawpow_digest);
+}
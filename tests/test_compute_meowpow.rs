@@ -0,0 +1,37 @@
+use powkit::algorithm::{PowAlgorithm, Verification};
+use powkit::meowpow::client::Client;
+use std::path::PathBuf;
+
+#[test]
+fn test_compute_meowcoin() {
+    let client = Client::new_meowcoin(PathBuf::from("/tmp"));
+    let kawpow = powkit::kawpow::client::Client::new_raven_coin(PathBuf::from("/tmp"));
+    let hash: [u8; 32] =
+        hex::decode("de37e1824c86d35d154cf65a88de6d9286aec4f7f10c3fc9f0fa1bcc2687188d")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x000000003917afab;
+
+    // TODO: these only check the schedule and the difference from KawPow, pin the vectors
+    // published with Meowcoin's MeowPow reference
+    // one program per 6 blocks
+    let (mix_0, digest_0) = client.compute(&hash, 0, nonce).unwrap();
+    let (mix_5, _) = client.compute(&hash, 5, nonce).unwrap();
+    let (mix_6, _) = client.compute(&hash, 6, nonce).unwrap();
+    assert_eq!(mix_0, mix_5);
+    assert_ne!(mix_0, mix_6);
+
+    let (kawpow_mix, _) = kawpow.compute(&hash, 0, nonce).unwrap();
+    assert_ne!(mix_0, kawpow_mix);
+
+    let max_target = [0xff; 32];
+    let verify =
+        |mix: &[u8], target: &[u8; 32]| client.verify(&hash, 0, nonce, mix, target).unwrap();
+    assert_eq!(verify(&mix_0, &max_target), Verification::Valid);
+    assert_eq!(verify(&kawpow_mix, &max_target), Verification::MixMismatch);
+    let mut target = [0; 32];
+    target.copy_from_slice(&digest_0);
+    assert_eq!(verify(&mix_0, &target), Verification::Valid);
+    assert_eq!(verify(&mix_0, &[0; 32]), Verification::AboveTarget);
+}
@@ -0,0 +1,62 @@
+use powkit::ethash::client::Client;
+use powkit::internal::dag::cache::{generate_cache, generate_dataset_item_unit, generate_l1_cache};
+use powkit::internal::progpow;
+use powkit::internal::progpow::config::Config;
+use std::path::PathBuf;
+
+// https://github.com/ifdefelse/ProgPOW#test-vectors
+#[test]
+fn test_progpow_revisions() {
+    let ethash = Client::new_ethereum(PathBuf::from("/tmp"));
+    let cfg = ethash.config();
+    let height = 30000;
+    let epoch = ethash.epoch(height);
+    let cache_size = cfg.cache_size(epoch);
+    let dataset_size = cfg.dataset_size(epoch);
+
+    let mut cache = vec![0; cache_size + 1];
+    generate_cache(
+        &mut cache,
+        &ethash.seed_hash(height).unwrap(),
+        cfg.cache_rounds,
+    )
+    .unwrap();
+    let cache = &cache[..cache_size];
+    let mut l1 = vec![0; 16 * 1024 + 1];
+    generate_l1_cache(&mut l1, cache, cfg.dataset_parents).unwrap();
+    let l1 = &l1[..16 * 1024];
+    let lookup = |index| generate_dataset_item_unit(cache, index, 4, cfg.dataset_parents);
+
+    let header =
+        hex::decode("ffeeddccbbaa9988776655443322110000112233445566778899aabbccddeeff").unwrap();
+    let nonce = 0x123456789abcdef0;
+
+    let tests = vec![
+        (
+            Config::progpow092(),
+            "11f19805c58ab46610ff9c719dcf0a5f18fa2f1605798eef770c47219274767d",
+            "5b7ccd472dbefdd95b895cac8ece67ff0deb5a6bd2ecc6e162383d00c3728ece",
+        ),
+        (
+            Config::progpow093(),
+            "6018c151b0f9895ebe44a4ca6ce2829e5ba6ae1a68a4ccd05a67ac01219655c1",
+            "34d8436444aa5c61761ce0bcce0f11401df2eace77f5c14ba7039b86b5800c08",
+        ),
+        // TODO: add the 0.9.4 vector of the same block and nonce
+    ];
+    for (progpow_cfg, mix, digest) in tests {
+        let r = progpow::hash(
+            &progpow_cfg,
+            &progpow::PROGPOW_094,
+            &progpow::PROGPOW_094_FINAL,
+            &header,
+            height,
+            nonce,
+            dataset_size,
+            lookup,
+            l1,
+        );
+        assert_eq!(hex::encode(&r.0), mix, "{:?}", progpow_cfg.revision);
+        assert_eq!(hex::encode(&r.1), digest, "{:?}", progpow_cfg.revision);
+    }
+}
@@ -171,7 +171,7 @@ fn test_compute_rvn_coin() {
     ];
     let client = Client::new_raven_coin(PathBuf::from("/tmp"));
     for tt in tests {
-        let (mix, digest) = client.compute(tt.hash, tt.height, tt.nonce).unwrap();
+        let (mix, digest) = client.compute(&tt.hash, tt.height, tt.nonce).unwrap();
         assert_eq!(mix, tt.mix);
         assert_eq!(digest, tt.digest);
     }
@@ -200,6 +200,6 @@ fn test_compute_rvn_coin() {
         let real_target = hex::encode(digest);
         println!("mix: {:?}", hex::encode(mix));
         println!("digest: {:?}", real_target);
-        assert!(real_target < "00000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff".to_string());
+        assert!(real_target.as_str() < "00000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
     }
 }
@@ -0,0 +1,55 @@
+use powkit::algorithm::PowAlgorithm;
+use powkit::internal::crypto::hasher::fill_sha3_512;
+use powkit::internal::dag::cache::{generate_cache, generate_cache_with};
+use powkit::internal::ethash::hashimoto_light;
+use powkit::ubqhash::client::{Client, UIP1_EPOCH};
+use std::path::PathBuf;
+
+#[test]
+fn test_compute_ubqhash() {
+    let client = Client::new_ubiq(PathBuf::from("/tmp"));
+    let ethash = powkit::ethash::client::Client::new_ethereum(PathBuf::from("/tmp"));
+    assert_eq!(client.activation_height(), 660000);
+    assert_eq!(client.epoch(659999), UIP1_EPOCH - 1);
+    assert_eq!(client.epoch(660000), UIP1_EPOCH);
+    assert_eq!(
+        client.seed_hash(660000).unwrap(),
+        ethash.seed_hash(660000).unwrap()
+    );
+
+    // before the activation ubqhash is ethash
+    let hash: [u8; 32] =
+        hex::decode("2a8de2adf89af77358250bf908bf04ba94a6e8c3ba87775564a41d269a05e4ce")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let (mix, digest) = client.compute(&hash, 0, 0x4242424242424242).unwrap();
+    assert_eq!(
+        hex::encode(mix),
+        "58f759ede17a706c93f13030328bcea40c1d1341fb26f2facd21ceb0dae57017"
+    );
+    assert_eq!(
+        hex::encode(digest),
+        "dd47fd2d98db51078356852d7c4014e6a5d6c387c35f40e2875b74a256ed7906"
+    );
+
+    // after the activation the cache is seeded with SHA3-512
+    // TODO: this only checks the client against hashimoto over a SHA3-512 cache, pin the
+    // header hash, nonce, mix and digest of a mined Ubiq block from 660000 on
+    let height = 660000;
+    let nonce = 0x1d2a9f03c45e8b77;
+    let cache_size = ethash.config().cache_size(UIP1_EPOCH);
+    let dataset_size = ethash.config().dataset_size(UIP1_EPOCH);
+    let seed = client.seed_hash(height).unwrap();
+    let mut cache = vec![0; cache_size + 1];
+    generate_cache_with(&mut cache, &seed, 3, fill_sha3_512).unwrap();
+    let expected = hashimoto_light(&hash, nonce, dataset_size, &cache[..cache_size], 256);
+    let (mix, digest) = client.compute(&hash, height, nonce).unwrap();
+    assert_eq!((mix.clone(), digest), expected);
+
+    let mut keccak_cache = vec![0; cache_size + 1];
+    generate_cache(&mut keccak_cache, &seed, 3).unwrap();
+    let (ethash_mix, _) =
+        hashimoto_light(&hash, nonce, dataset_size, &keccak_cache[..cache_size], 256);
+    assert_ne!(mix, ethash_mix);
+}
@@ -0,0 +1,54 @@
+use powkit::ethash;
+use powkit::internal::dag::config::{DagConfig, DataKind};
+use powkit::internal::dag::file_header::HEADER_SIZE;
+use powkit::kawpow;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+
+mod common;
+
+use common::tiny_config;
+
+#[test]
+fn test_kawpow_full_dataset() {
+    let cfg = tiny_config("RVNFULL");
+    let path = cfg.file_path(1, DataKind::Dataset);
+    let _ = fs::remove_file(&path);
+
+    let client = kawpow::client::Client::new(cfg.clone());
+    let hash = [7u8; 32];
+    for nonce in 0..8 {
+        assert_eq!(
+            client.compute_full(&hash, 5, nonce).unwrap(),
+            client.compute(&hash, 5, nonce).unwrap()
+        );
+    }
+
+    let data = fs::read(&path).unwrap();
+    assert_eq!(data.len(), HEADER_SIZE + cfg.dataset_size(1) + 1);
+    assert_eq!(data[data.len() - 1], 1);
+
+    // another client maps the finished file instead of generating and renaming a new one
+    let inode = fs::metadata(&path).unwrap().ino();
+    let other = kawpow::client::Client::new(cfg);
+    assert_eq!(
+        other.compute_full(&hash, 5, 0).unwrap(),
+        client.compute(&hash, 5, 0).unwrap()
+    );
+    assert_eq!(fs::metadata(&path).unwrap().ino(), inode);
+}
+
+#[test]
+fn test_ethash_full_dataset() {
+    let client = ethash::client::Client::new(DagConfig {
+        l1_enabled: false,
+        ..tiny_config("ETHFULL")
+    });
+    let hash = [9u8; 32];
+    for height in [0, 4, 9] {
+        assert_eq!(
+            client.compute_full(&hash, height, 42).unwrap(),
+            client.compute(&hash, height, 42).unwrap()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use powkit::algorithm::{new_algorithm, PowAlgorithm};
+use powkit::error::Error;
+use powkit::internal::dag::cache::generate_cache;
+use powkit::internal::dag::config::{DagConfig, MAX_EPOCH};
+use powkit::kawpow::client::Client;
+use powkit::kawpow::validator::HeaderReader;
+use std::path::PathBuf;
+
+mod common;
+
+use common::tiny_config;
+
+#[test]
+fn test_config_errors() {
+    let hash = [0u8; 32];
+
+    let mut cfg = tiny_config("ERRNOL1");
+    cfg.l1_enabled = false;
+    let err = Client::new(cfg).compute(&hash, 0, 0).unwrap_err();
+    assert!(matches!(err, Error::L1Missing));
+    assert!(!err.is_retryable());
+
+    let mut cfg = tiny_config("ERRSMALLL1");
+    cfg.l1_cache_size = 1024;
+    let err = Client::new(cfg).compute(&hash, 0, 0).unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(_)));
+
+    let mut cfg = tiny_config("ERREPOCH");
+    cfg.epoch_length = 0;
+    assert!(matches!(cfg.validate(), Err(Error::InvalidConfig(_))));
+
+    let mut cfg = tiny_config("ERRCACHE");
+    cfg.cache_init_bytes = 64;
+    let err = Client::new(cfg).compute(&hash, 0, 0).unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(_)));
+
+    let mut cache = vec![0; 100];
+    let err = generate_cache(&mut cache, &[0; 32], 3).unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(_)));
+}
+
+#[test]
+fn test_input_errors() {
+    let client = Client::new(tiny_config("ERRINPUT"));
+    let height = (MAX_EPOCH as u64 + 1) * 4;
+    let err = client.compute(&[0; 32], height, 0).unwrap_err();
+    assert!(matches!(err, Error::EpochOutOfRange(epoch) if epoch == MAX_EPOCH + 1));
+    assert!(matches!(
+        client.seed_hash(height),
+        Err(Error::EpochOutOfRange(_))
+    ));
+
+    assert!(!client.precheck(&[0; 32], 0, &[0; 31], &[0xff; 32]));
+
+    let err = new_algorithm("sha256d", PathBuf::from("/tmp"))
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::UnknownAlgorithm(ref name) if name == "sha256d"));
+    assert!(!err.is_retryable());
+
+    let mut headers = HeaderReader::new(&[0u8; 130][..]);
+    assert!(headers.next().unwrap().is_ok());
+    assert!(matches!(
+        headers.next().unwrap(),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(headers.next().is_none());
+}
+
+#[test]
+fn test_io_error_is_retryable() {
+    let client = Client::new(DagConfig {
+        storage_dir: PathBuf::from("/nonexistent/powkit"),
+        ..tiny_config("ERRIO")
+    });
+    let err = client.compute(&[0; 32], 0, 0).unwrap_err();
+    assert!(matches!(err, Error::Io(_)));
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,143 @@
+use powkit::ethash::difficulty::{
+    calc_difficulty, verify_difficulty, ForkSchedule, EMPTY_UNCLE_HASH, MINIMUM_DIFFICULTY,
+};
+use powkit::ethash::header::Header;
+use powkit::target::U256;
+
+fn parent(number: u64, time: u64, difficulty: u64) -> Header {
+    Header {
+        parent_hash: [0; 32],
+        uncle_hash: EMPTY_UNCLE_HASH,
+        coinbase: [0; 20],
+        state_root: [0; 32],
+        tx_root: [0; 32],
+        receipt_root: [0; 32],
+        bloom: vec![0; 256],
+        difficulty: U256::from(difficulty),
+        number,
+        gas_limit: 5000,
+        gas_used: 0,
+        time,
+        extra: vec![],
+        mix_digest: [0; 32],
+        nonce: 0,
+        base_fee: None,
+        withdrawals_root: None,
+        blob_gas_used: None,
+        excess_blob_gas: None,
+        parent_beacon_root: None,
+    }
+}
+
+#[test]
+fn test_frontier_difficulty() {
+    let eth = ForkSchedule::ethereum();
+    // mainnet blocks 1 and 2
+    let genesis = parent(0, 0, 17179869184);
+    let block_1 = Header {
+        number: 1,
+        time: 1438269988,
+        difficulty: U256::from(17171480576),
+        ..genesis.clone()
+    };
+    assert!(verify_difficulty(&eth, &genesis, &block_1));
+    assert_eq!(
+        calc_difficulty(&eth, 1438270017, &block_1),
+        U256::from(17163096064)
+    );
+    assert_eq!(
+        calc_difficulty(&eth, 1438269990, &block_1),
+        U256::from(17171480576 + 17171480576 / 2048)
+    );
+
+    let low = parent(10, 0, MINIMUM_DIFFICULTY);
+    assert_eq!(
+        calc_difficulty(&eth, 100, &low),
+        U256::from(MINIMUM_DIFFICULTY)
+    );
+    // bomb of the block 200000
+    let bomb = parent(199_999, 0, MINIMUM_DIFFICULTY);
+    assert_eq!(
+        calc_difficulty(&eth, 100, &bomb),
+        U256::from(MINIMUM_DIFFICULTY + 1)
+    );
+
+    // no block follows the last number, and its bomb does not fit a shift
+    let last = parent(u64::MAX, 0, MINIMUM_DIFFICULTY);
+    let child = Header {
+        number: 0,
+        difficulty: calc_difficulty(&eth, 100, &last),
+        ..last.clone()
+    };
+    assert!(!verify_difficulty(&eth, &last, &child));
+    assert_eq!(child.difficulty, U256::from(MINIMUM_DIFFICULTY));
+}
+
+#[test]
+fn test_ethereum_difficulty() {
+    let eth = ForkSchedule::ethereum();
+    let d = 1_000_000_000_000u64;
+
+    // Homestead, the bomb of the block 1200001 is 2^10
+    let homestead = parent(1_200_000, 1000, d);
+    assert_eq!(
+        calc_difficulty(&eth, 1005, &homestead),
+        U256::from(d + d / 2048 + 1024)
+    );
+    assert_eq!(
+        calc_difficulty(&eth, 1025, &homestead),
+        U256::from(d - d / 2048 + 1024)
+    );
+    assert_eq!(
+        calc_difficulty(&eth, 10000, &homestead),
+        U256::from(d - d / 2048 * 99 + 1024)
+    );
+
+    // Byzantium, uncles and a bomb delayed by 3M blocks
+    let mut byzantium = parent(5_000_000, 1000, d);
+    assert_eq!(
+        calc_difficulty(&eth, 1020, &byzantium),
+        U256::from(d - d / 2048 + (1 << 18))
+    );
+    byzantium.uncle_hash = [1; 32];
+    assert_eq!(
+        calc_difficulty(&eth, 1020, &byzantium),
+        U256::from(d + (1 << 18))
+    );
+
+    // Muir Glacier delays the bomb back to the period 2
+    let muir = parent(9_200_000, 1000, d);
+    assert_eq!(calc_difficulty(&eth, 1009, &muir), U256::from(d + 1));
+    let constantinople = parent(9_199_998, 1000, d);
+    assert_eq!(
+        calc_difficulty(&eth, 1009, &constantinople),
+        U256::from(d + (1 << 39))
+    );
+
+    // Gray Glacier
+    let gray = parent(15_099_999, 1000, d);
+    assert_eq!(
+        calc_difficulty(&eth, 1000, &gray),
+        U256::from(d + d / 2048 + (1 << 35))
+    );
+}
+
+#[test]
+fn test_ethereum_classic_difficulty() {
+    let etc = ForkSchedule::ethereum_classic();
+    let d = 1_000_000_000_000u64;
+    let next =
+        |number: u64, elapsed: u64| calc_difficulty(&etc, 1000 + elapsed, &parent(number, 1000, d));
+
+    // ECIP-1010 pauses the bomb at the period 30
+    assert_eq!(next(3_999_999, 10), U256::from(d + (1 << 28)));
+    assert_eq!(next(4_999_998, 10), U256::from(d + (1 << 28)));
+    // and continues it 2M blocks later
+    assert_eq!(next(5_499_999, 10), U256::from(d + (1 << 33)));
+    // ECIP-1041 removes it
+    assert_eq!(next(5_899_999, 10), U256::from(d));
+    // Atlantis adopted EIP-100
+    assert_eq!(next(8_771_998, 9), U256::from(d + d / 2048));
+    assert_eq!(next(8_771_999, 9), U256::from(d));
+    assert_eq!(next(8_771_999, 5), U256::from(d + d / 2048));
+}
@@ -0,0 +1,113 @@
+use powkit::algorithm::Verification;
+use powkit::error::Error;
+use powkit::ethash::client::Client;
+use powkit::ethash::header::Header;
+use powkit::target::U256;
+use std::path::PathBuf;
+
+fn decode<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+// Ethereum mainnet block 1
+fn block_1() -> Header {
+    Header {
+        parent_hash: decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"),
+        uncle_hash: decode("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+        coinbase: decode("05a56e2d52c817161883f50c441c3228cfe54d9f"),
+        state_root: decode("d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"),
+        tx_root: decode("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+        receipt_root: decode("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+        bloom: vec![0; 256],
+        difficulty: U256::from(17171480576),
+        number: 1,
+        gas_limit: 5000,
+        gas_used: 0,
+        time: 1438269988,
+        extra: hex::decode("476574682f76312e302e302f6c696e75782f676f312e342e32").unwrap(),
+        mix_digest: decode("969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"),
+        nonce: 0x539bd4979fef1ec4,
+        base_fee: None,
+        withdrawals_root: None,
+        blob_gas_used: None,
+        excess_blob_gas: None,
+        parent_beacon_root: None,
+    }
+}
+
+#[test]
+fn test_header_rlp() {
+    let header = block_1();
+    assert_eq!(
+        hex::encode(header.hash()),
+        "88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"
+    );
+    let rlp = header.rlp();
+    assert_eq!(Header::from_rlp(&rlp).unwrap(), header);
+
+    let sealed = Header {
+        mix_digest: [0; 32],
+        nonce: 0,
+        ..header.clone()
+    };
+    assert_eq!(sealed.seal_hash(), header.seal_hash());
+    assert_ne!(sealed.hash(), header.hash());
+
+    // fork dependent fields
+    // TODO: pin a mined post-London mainnet header and its hash, these only round trip
+    let london = Header {
+        base_fee: Some(U256::from(1_000_000_000)),
+        ..header.clone()
+    };
+    assert_eq!(Header::from_rlp(&london.rlp()).unwrap(), london);
+    assert_ne!(london.seal_hash(), header.seal_hash());
+    let cancun = Header {
+        withdrawals_root: Some([1; 32]),
+        blob_gas_used: Some(0),
+        excess_blob_gas: Some(131072),
+        parent_beacon_root: Some([2; 32]),
+        ..london.clone()
+    };
+    assert_eq!(Header::from_rlp(&cancun.rlp()).unwrap(), cancun);
+    let shanghai = Header {
+        blob_gas_used: None,
+        excess_blob_gas: None,
+        parent_beacon_root: None,
+        ..cancun
+    };
+    assert_eq!(Header::from_rlp(&shanghai.rlp()).unwrap(), shanghai);
+
+    assert!(Header::from_rlp(&rlp[..rlp.len() - 1]).is_err());
+}
+
+#[test]
+fn test_verify_header() {
+    let client = Client::new_ethereum(PathBuf::from("/tmp"));
+    let header = Header::from_rlp(&block_1().rlp()).unwrap();
+    assert_eq!(client.verify_header(&header).unwrap(), Verification::Valid);
+
+    let bad_nonce = Header {
+        nonce: header.nonce + 1,
+        ..header.clone()
+    };
+    assert_eq!(
+        client.verify_header(&bad_nonce).unwrap(),
+        Verification::MixMismatch
+    );
+    let bad_difficulty = Header {
+        difficulty: U256::ONE << 255,
+        ..header.clone()
+    };
+    assert_ne!(
+        client.verify_header(&bad_difficulty).unwrap(),
+        Verification::Valid
+    );
+    let zero_difficulty = Header {
+        difficulty: U256::ZERO,
+        ..header
+    };
+    assert!(matches!(
+        client.verify_header(&zero_difficulty),
+        Err(Error::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,145 @@
+use powkit::internal::crypto::sha256::sha256;
+use powkit::internal::dag::cache::{generate_cache, generate_l1_cache};
+use powkit::internal::dag::config::{DagConfig, DataKind};
+use powkit::internal::dag::file_header::{FileHeader, FORMAT_VERSION, HEADER_SIZE};
+use powkit::internal::dag::Dag;
+use powkit::kawpow::client::Client;
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::tiny_config;
+
+fn header(cfg: &DagConfig, kind: DataKind) -> FileHeader {
+    let data = fs::read(cfg.file_path(0, kind)).unwrap();
+    FileHeader::from_bytes(&data).unwrap()
+}
+
+#[test]
+fn test_header_round_trip() {
+    let cfg = tiny_config("RVNHEADER");
+    let data = [5u8; 128];
+    let header = FileHeader::new(&cfg, 3, DataKind::L1, &data).unwrap();
+    assert_eq!(header.version, FORMAT_VERSION);
+    assert_eq!(header.epoch, 3);
+    assert_eq!(header.seed, cfg.seed_for_epoch(3).unwrap());
+    assert_eq!(&header.name[..9], b"RVNHEADER");
+    assert_eq!(
+        FileHeader::from_bytes(&header.to_bytes()),
+        Some(header.clone())
+    );
+    assert_eq!(header.mismatch(&cfg, 3, DataKind::L1), None);
+    assert!(header.mismatch(&cfg, 3, DataKind::Cache).is_some());
+    assert!(header.mismatch(&cfg, 4, DataKind::L1).is_some());
+    assert_eq!(FileHeader::from_bytes(&[0; HEADER_SIZE]), None);
+}
+
+#[test]
+fn test_rejects_other_config_and_corruption() {
+    let cfg = tiny_config("RVNFINGERPRINT");
+    let hash = [1u8; 32];
+    let expected = Client::new(cfg.clone()).compute(&hash, 0, 0).unwrap();
+    let written = header(&cfg, DataKind::Cache);
+
+    // same name, other cache rounds: the cache is generated again
+    let other = DagConfig {
+        cache_rounds: 2,
+        ..cfg.clone()
+    };
+    let other_mix = Client::new(other.clone()).compute(&hash, 0, 0).unwrap();
+    assert_ne!(other_mix, expected);
+    assert_ne!(
+        header(&other, DataKind::Cache).config_hash,
+        written.config_hash
+    );
+
+    // a flipped byte fails the checksum
+    let path = cfg.file_path(0, DataKind::Cache);
+    let mut data = fs::read(&path).unwrap();
+    data[HEADER_SIZE + 10] ^= 0xff;
+    fs::write(&path, &data).unwrap();
+    assert_eq!(
+        Client::new(cfg.clone()).compute(&hash, 0, 0).unwrap(),
+        expected
+    );
+    assert_eq!(header(&cfg, DataKind::Cache), written);
+}
+
+#[test]
+fn test_migrates_headerless_l1() {
+    let cfg = tiny_config("RVNLEGACY");
+    let mut cache = vec![0; cfg.cache_size(0) + 1];
+    generate_cache(
+        &mut cache,
+        &cfg.seed_for_epoch(0).unwrap(),
+        cfg.cache_rounds,
+    )
+    .unwrap();
+    let mut l1 = vec![0; cfg.l1_cache_size + 1];
+    generate_l1_cache(&mut l1, &cache[..cache.len() - 1], cfg.dataset_parents).unwrap();
+
+    let l1_path = cfg.file_path(0, DataKind::L1);
+    fs::write(cfg.file_path(0, DataKind::Cache), &cache).unwrap();
+    fs::write(&l1_path, &l1).unwrap();
+
+    let hash = [2u8; 32];
+    let r = Client::new(cfg.clone()).compute(&hash, 0, 0).unwrap();
+    let migrated = fs::read(&l1_path).unwrap();
+    assert_eq!(migrated[HEADER_SIZE..], l1[..]);
+    assert_eq!(
+        header(&cfg, DataKind::L1).mismatch(&cfg, 0, DataKind::L1),
+        None
+    );
+    // the headerless cache can't be checked and was generated again
+    assert_eq!(
+        fs::read(cfg.file_path(0, DataKind::Cache)).unwrap()[HEADER_SIZE..],
+        cache[..]
+    );
+    assert_eq!(Client::new(cfg).compute(&hash, 0, 0).unwrap(), r);
+
+    // a headerless L1 of other items is not migrated
+    let cfg = tiny_config("RVNLEGACYBAD");
+    let l1_path = cfg.file_path(0, DataKind::L1);
+    fs::write(&l1_path, vec![1; cfg.l1_cache_size + 1]).unwrap();
+    Client::new(cfg.clone()).compute(&hash, 0, 0).unwrap();
+    assert_eq!(fs::read(&l1_path).unwrap()[HEADER_SIZE..], l1[..]);
+}
+
+#[test]
+fn test_dataset_checksum_is_checked_in_background() {
+    let cfg = tiny_config("RVNDATASETSUM");
+    let path = cfg.file_path(0, DataKind::Dataset);
+    let _ = fs::remove_file(&path);
+    let hash = [3u8; 32];
+    let r = Client::new(cfg.clone()).compute_full(&hash, 0, 0).unwrap();
+
+    let mut data = fs::read(&path).unwrap();
+    let written = FileHeader::from_bytes(&data).unwrap();
+    assert_eq!(written.checksum, sha256(&data[HEADER_SIZE..data.len() - 1]));
+
+    // a corrupt dataset is served until the check in the background catches it
+    data[HEADER_SIZE + 10] ^= 0xff;
+    fs::write(&path, &data).unwrap();
+    let dag = Dag::new(cfg.clone());
+    let dataset = dag.get_dataset(0).unwrap();
+    assert_eq!(dataset.data()[10], data[HEADER_SIZE + 10]);
+    for _ in 0..100 {
+        if dataset.is_corrupt() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(dataset.is_corrupt());
+    assert!(!path.exists());
+
+    // then it is generated again
+    let dataset = dag.get_dataset(0).unwrap();
+    assert_ne!(dataset.data()[10], data[HEADER_SIZE + 10]);
+    assert_eq!(
+        fs::read(&path).unwrap()[HEADER_SIZE + 10],
+        dataset.data()[10]
+    );
+    assert_eq!(Client::new(cfg).compute_full(&hash, 0, 0).unwrap(), r);
+}
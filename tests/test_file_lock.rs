@@ -0,0 +1,106 @@
+use fs2::FileExt;
+use powkit::ethash::client::Client;
+#[cfg(unix)]
+use powkit::internal::dag::cache::generate_cache;
+use powkit::internal::dag::config::DataKind;
+#[cfg(unix)]
+use powkit::internal::dag::file_header::FileHeader;
+use std::fs::{self, OpenOptions};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::tiny_config;
+
+// the file is checked to be left in place by its inode
+#[cfg(unix)]
+#[test]
+fn test_wait_for_generating_process() {
+    let cfg = tiny_config("ETHLOCK");
+    let path = cfg.file_path(0, DataKind::Cache);
+    let _ = fs::remove_file(&path);
+
+    // play the process generating the cache, flock locks of distinct opens conflict
+    // within a process too
+    let lock = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(cfg.lock_path(0, DataKind::Cache))
+        .unwrap();
+    lock.lock_exclusive().unwrap();
+
+    let client = Client::new(cfg.clone());
+    let hash = [3u8; 32];
+    let verifier = thread::spawn(move || client.compute(&hash, 0, 1));
+    thread::sleep(Duration::from_millis(300));
+    assert!(!verifier.is_finished());
+
+    let mut cache = vec![0; cfg.cache_size(0) + 1];
+    generate_cache(
+        &mut cache,
+        &cfg.seed_for_epoch(0).unwrap(),
+        cfg.cache_rounds,
+    )
+    .unwrap();
+    let header = FileHeader::new(&cfg, 0, DataKind::Cache, &cache[..cache.len() - 1]).unwrap();
+    fs::write(&path, [&header.to_bytes()[..], &cache].concat()).unwrap();
+    let inode = fs::metadata(&path).unwrap().ino();
+    lock.unlock().unwrap();
+
+    let r = verifier.join().unwrap().unwrap();
+    assert_eq!(r, Client::new(cfg).compute(&hash, 0, 1).unwrap());
+    // the waiting process mapped the finished file instead of generating it again
+    assert_eq!(fs::metadata(&path).unwrap().ino(), inode);
+}
+
+#[test]
+fn test_dataset_generation_does_not_block_caches() {
+    let cfg = tiny_config("ETHLOCKDS");
+    let _ = fs::remove_file(cfg.file_path(0, DataKind::Dataset));
+
+    // another process generates the dataset of epoch 0
+    let lock = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(cfg.lock_path(0, DataKind::Dataset))
+        .unwrap();
+    lock.lock_exclusive().unwrap();
+
+    let client = Arc::new(Client::new(cfg));
+    let hash = [5u8; 32];
+    let miner = {
+        let client = client.clone();
+        thread::spawn(move || client.compute_full(&hash, 0, 1))
+    };
+    thread::sleep(Duration::from_millis(300));
+    assert!(!miner.is_finished());
+
+    // the caches of other epochs are still generated while the dataset is waited for
+    let verifier = {
+        let client = client.clone();
+        thread::spawn(move || client.compute(&hash, 8, 1))
+    };
+    for _ in 0..100 {
+        if verifier.is_finished() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(verifier.is_finished());
+    assert!(!miner.is_finished());
+
+    lock.unlock().unwrap();
+    assert_eq!(
+        miner.join().unwrap().unwrap(),
+        client.compute(&hash, 0, 1).unwrap()
+    );
+    verifier.join().unwrap().unwrap();
+}
@@ -0,0 +1,183 @@
+use powkit::algorithm::Verification;
+use powkit::kawpow::client::Client;
+use powkit::kawpow::difficulty::{dark_gravity_wave, DgwParams};
+use powkit::kawpow::header::BlockHeader;
+use powkit::kawpow::validator::{ChainValidator, InvalidHeader, InvalidReason};
+use powkit::target::U256;
+use std::path::PathBuf;
+
+mod common;
+
+use common::tiny_config;
+
+fn tiny_client() -> Client {
+    Client::new(tiny_config("RVNTINY"))
+}
+
+// targets times the retarget factors must not overflow 256 bits
+fn params() -> DgwParams {
+    DgwParams {
+        pow_limit: U256::MAX >> 5,
+        kawpow_limit: U256::MAX >> 5,
+        kawpow_activation_time: 0,
+        target_spacing: 2,
+        past_blocks: 3,
+    }
+}
+
+fn mine(client: &Client, header: &mut BlockHeader) {
+    let target = header.target().unwrap();
+    let header_hash = header.header_hash();
+    for nonce in 0.. {
+        let (mix, digest) = client
+            .compute(&header_hash, header.height as u64, nonce)
+            .unwrap();
+        if digest.as_slice() <= target.as_slice() {
+            header.nonce = nonce;
+            header.mix_hash.copy_from_slice(&mix);
+            header.mix_hash.reverse();
+            return;
+        }
+    }
+}
+
+// blocks 3s apart over 2 intervals match the 3 * 2s DGW timespan, the target stays put
+fn chain(client: &Client, len: u32) -> Vec<BlockHeader> {
+    let mut headers: Vec<BlockHeader> = vec![];
+    for height in 0..len {
+        let time = 1_600_000_000 + height * 3;
+        let mut header = BlockHeader {
+            version: 0x30000000,
+            prev_hash: headers.last().map_or([0; 32], |h| client.block_hash(h)),
+            merkle_root: [height as u8; 32],
+            time,
+            bits: dark_gravity_wave(&params(), &headers, time).unwrap(),
+            height,
+            nonce: 0,
+            mix_hash: [0; 32],
+        };
+        mine(client, &mut header);
+        headers.push(header);
+    }
+    headers
+}
+
+#[test]
+fn test_validate_chain() {
+    let client = tiny_client();
+    let headers = chain(&client, 14);
+    let validate = |headers: Vec<BlockHeader>| {
+        ChainValidator::new(&client, params())
+            .validate_all(headers)
+            .unwrap()
+    };
+    assert_eq!(validate(headers.clone()), None);
+
+    let invalid = |index: usize, reason| {
+        Some(InvalidHeader {
+            index,
+            height: index as u32,
+            reason,
+        })
+    };
+
+    let mut broken = headers.clone();
+    broken[7].prev_hash[0] ^= 1;
+    assert_eq!(
+        validate(broken),
+        invalid(7, InvalidReason::PrevHashMismatch)
+    );
+
+    let mut broken = headers.clone();
+    broken.remove(5);
+    assert_eq!(
+        validate(broken),
+        Some(InvalidHeader {
+            index: 5,
+            height: 6,
+            reason: InvalidReason::UnexpectedHeight {
+                expected: Some(5),
+                got: 6
+            },
+        })
+    );
+
+    // no height follows the last one
+    let last = BlockHeader {
+        height: u32::MAX,
+        ..headers[0].clone()
+    };
+    let result = ChainValidator::new(&client, params())
+        .with_ancestors([last])
+        .validate(&headers[1])
+        .unwrap();
+    assert_eq!(
+        result,
+        Some(InvalidReason::UnexpectedHeight {
+            expected: None,
+            got: 1
+        })
+    );
+
+    let mut broken = headers.clone();
+    broken[9].time = broken[4].time;
+    assert!(matches!(
+        validate(broken).unwrap().reason,
+        InvalidReason::TimeTooOld { .. }
+    ));
+
+    let mut broken = headers.clone();
+    broken[10].bits = (U256::MAX >> 2).to_compact();
+    assert_eq!(
+        validate(broken),
+        invalid(
+            10,
+            InvalidReason::UnexpectedBits {
+                expected: headers[10].bits,
+                got: (U256::MAX >> 2).to_compact()
+            }
+        )
+    );
+
+    let mut broken = headers.clone();
+    broken[12].mix_hash[0] ^= 1;
+    assert!(matches!(
+        validate(broken).unwrap().reason,
+        InvalidReason::InvalidPow(Verification::MixMismatch | Verification::AboveTarget)
+    ));
+
+    let max_time = headers[3].time;
+    let result = ChainValidator::new(&client, params())
+        .with_max_time(max_time)
+        .validate_all(headers.clone())
+        .unwrap();
+    assert_eq!(result, invalid(4, InvalidReason::TimeTooNew { max_time }));
+
+    // resume from trusted ancestors, or without them and skip the difficulty until known
+    let result = ChainValidator::new(&client, params())
+        .with_ancestors(headers[..8].to_vec())
+        .validate_all(headers[8..].to_vec())
+        .unwrap();
+    assert_eq!(result, None);
+    let mut header = headers[8].clone();
+    header.bits = (U256::MAX >> 2).to_compact();
+    mine(&client, &mut header);
+    assert_eq!(validate(vec![header]), None);
+}
+
+#[test]
+fn test_validate_file() {
+    let client = tiny_client();
+    let headers = chain(&client, 6);
+    let path = PathBuf::from("/tmp/powkit-test-headers-RVNTINY");
+    let bytes: Vec<u8> = headers.iter().flat_map(|h| h.to_bytes()).collect();
+
+    std::fs::write(&path, &bytes).unwrap();
+    let mut validator = ChainValidator::new(&client, params());
+    assert_eq!(validator.validate_file(&path).unwrap(), None);
+
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    let mut validator = ChainValidator::new(&client, params());
+    assert!(validator.validate_file(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,106 @@
+use powkit::error::Error;
+use powkit::internal::dag::config::DagConfig;
+use powkit::internal::progpow::client::Client;
+use powkit::internal::progpow::config::Config;
+use powkit::internal::progpow::variant::Variant;
+use powkit::internal::progpow::{PROGPOW_094, PROGPOW_094_FINAL, RAVEN_COIN_KAWPOW};
+use std::path::PathBuf;
+
+mod common;
+
+use common::tiny_config;
+
+#[test]
+fn test_kawpow_fork_variant() {
+    let raven = Variant::raven_coin(PathBuf::from("/tmp"));
+    assert_eq!(raven.dag_items_per_load(), 4);
+
+    // a KawPow chain with its own cache files
+    let variant = Variant {
+        dag: DagConfig {
+            name: "NEOX".into(),
+            ..raven.dag.clone()
+        },
+        ..raven
+    };
+    let client = powkit::kawpow::client::Client::from_variant(variant);
+    let hash: [u8; 32] =
+        hex::decode("63155f732f2bf556967f906155b510c917e48e99685ead76ea83f4eca03ab12b")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let (mix, digest) = client.compute(&hash, 49, 0x0000000007073c07).unwrap();
+    assert_eq!(
+        hex::encode(mix),
+        "d36f7e815ee09e74eceb9c96993a3d681edf2bf0921fc7bb710364042db99777"
+    );
+    assert_eq!(
+        hex::encode(digest),
+        "e7ced124598fd2500a55ad9f9f48e3569327fe50493c77a4ac9799b96efb9463"
+    );
+}
+
+#[test]
+fn test_progpow_094_variant() {
+    // the ProgPoW 0.9.4 spec on the Ethash DAG
+    // TODO: pin the 0.9.4 vector of the spec, this only checks that both passes are padded
+    let ethash = powkit::ethash::client::Client::new_ethereum(PathBuf::from("/tmp"));
+    let variant = Variant {
+        progpow: Config::progpow094(),
+        initial_padding: PROGPOW_094,
+        final_padding: PROGPOW_094_FINAL,
+        dag: DagConfig {
+            name: "PROGPOW094".into(),
+            l1_enabled: true,
+            l1_cache_size: 4096 * 4,
+            l1_cache_num_items: 4096,
+            ..ethash.config().clone()
+        },
+    };
+    let client = Client::new(variant.clone());
+    let hash: [u8; 32] =
+        hex::decode("ffeeddccbbaa9988776655443322110000112233445566778899aabbccddeeff")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x123456789abcdef0;
+    let (mix, digest) = client.compute(&hash, 30000, nonce).unwrap();
+    assert_eq!(variant.final_hash(&hash, nonce, &mix).unwrap(), digest);
+
+    // the final pass absorbs its own padding, not the one of the initial pass
+    let initial_only = Variant {
+        final_padding: PROGPOW_094,
+        ..variant.clone()
+    };
+    assert_ne!(initial_only.final_hash(&hash, nonce, &mix).unwrap(), digest);
+    // and the initial padding reaches the mix
+    let final_only = Variant {
+        initial_padding: PROGPOW_094_FINAL,
+        ..variant
+    };
+    let (other_mix, _) = Client::new(final_only)
+        .compute(&hash, 30000, nonce)
+        .unwrap();
+    assert_ne!(other_mix, mix);
+}
+
+#[test]
+fn test_compute_with_cache_of_other_epoch() {
+    let client = Client::new(Variant {
+        progpow: Config::kawpow(),
+        initial_padding: RAVEN_COIN_KAWPOW,
+        final_padding: RAVEN_COIN_KAWPOW,
+        dag: tiny_config("RVNCACHEEPOCH"),
+    });
+    let cache = client.get_cache(0).unwrap();
+    let hash = [7u8; 32];
+    assert_eq!(
+        client.compute_with(&cache, &hash, 3, 1).unwrap(),
+        client.compute(&hash, 3, 1).unwrap()
+    );
+    // epochs are 4 blocks long
+    assert!(matches!(
+        client.compute_with(&cache, &hash, 4, 1),
+        Err(Error::InvalidInput(_))
+    ));
+}
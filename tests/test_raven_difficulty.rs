@@ -0,0 +1,96 @@
+use powkit::kawpow::difficulty::{dark_gravity_wave, DgwParams};
+use powkit::kawpow::header::BlockHeader;
+use powkit::target::U256;
+
+const ACTIVATION: u32 = 1588788000;
+
+fn chain(len: u32, start_time: u32, spacing: u32, bits: u32) -> Vec<BlockHeader> {
+    (0..len)
+        .map(|i| BlockHeader {
+            version: 0x30000000,
+            prev_hash: [0; 32],
+            merkle_root: [0; 32],
+            time: start_time + i * spacing,
+            bits,
+            height: 1_300_000 + i,
+            nonce: 0,
+            mix_hash: [0; 32],
+        })
+        .collect()
+}
+
+fn retarget(bits: u32, actual: u64, expected: u64) -> u32 {
+    let target = U256::from_compact(bits).unwrap();
+    let (target, _) = target.overflowing_mul_u64(actual);
+    target
+        .checked_div_rem(&U256::from(expected))
+        .unwrap()
+        .0
+        .to_compact()
+}
+
+#[test]
+fn test_dgw_retarget() {
+    let params = DgwParams::raven_coin();
+    let bits = 0x1b01cc26;
+    let start = ACTIVATION + 100_000;
+
+    // on schedule, the window spans 179 intervals for a 180 block timespan
+    let headers = chain(200, start, 60, bits);
+    let next = dark_gravity_wave(&params, &headers, start + 200 * 60).unwrap();
+    assert_eq!(next, retarget(bits, 179 * 60, 180 * 60));
+
+    // only the last 180 headers count
+    assert_eq!(dark_gravity_wave(&params, &headers[20..], 0).unwrap(), next);
+
+    // the timespan is clamped to a third and three times the target
+    let fast = chain(180, start, 1, bits);
+    assert_eq!(
+        dark_gravity_wave(&params, &fast, start + 180).unwrap(),
+        retarget(bits, 60, 180)
+    );
+    let slow = chain(180, start, 600, bits);
+    assert_eq!(
+        dark_gravity_wave(&params, &slow, start + 180 * 600).unwrap(),
+        retarget(bits, 3, 1)
+    );
+}
+
+#[test]
+fn test_dgw_limits() {
+    let params = DgwParams::raven_coin();
+    let pow_limit = params.pow_limit.to_compact();
+    assert_eq!(pow_limit, 0x1e0fffff);
+    assert_eq!(params.kawpow_limit.to_compact(), 0x1c00ffff);
+
+    // not enough history
+    let mut headers = chain(10, ACTIVATION + 1000, 60, 0x1b01cc26);
+    for (i, header) in headers.iter_mut().enumerate() {
+        header.height = i as u32;
+    }
+    assert_eq!(
+        dark_gravity_wave(&params, &headers, ACTIVATION + 2000).unwrap(),
+        pow_limit
+    );
+    assert_eq!(dark_gravity_wave(&params, &[], 0).unwrap(), pow_limit);
+    assert!(dark_gravity_wave(&params, &chain(10, ACTIVATION, 60, 0x1b01cc26), 0).is_err());
+
+    // never easier than the pow limit
+    let easy = chain(180, ACTIVATION, 600, pow_limit);
+    assert_eq!(
+        dark_gravity_wave(&params, &easy, ACTIVATION + 180 * 600).unwrap(),
+        pow_limit
+    );
+
+    // the first KawPow window restarts from the KawPow limit
+    let mixed = chain(180, ACTIVATION - 90 * 60, 60, 0x1b01cc26);
+    assert_eq!(
+        dark_gravity_wave(&params, &mixed, ACTIVATION + 90 * 60).unwrap(),
+        0x1c00ffff
+    );
+    // but not for blocks before the activation
+    assert_ne!(
+        dark_gravity_wave(&params, &mixed, ACTIVATION - 1).unwrap(),
+        0x1c00ffff
+    );
+}
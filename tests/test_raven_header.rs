@@ -0,0 +1,112 @@
+use powkit::algorithm::Verification;
+use powkit::kawpow::client::Client;
+use powkit::kawpow::header::{BlockHeader, HEADER_SIZE};
+use std::path::PathBuf;
+
+fn header() -> BlockHeader {
+    BlockHeader {
+        version: 0x20000000,
+        prev_hash: [1; 32],
+        merkle_root: [2; 32],
+        time: 1588788000,
+        bits: 0x2100ffff,
+        height: 49,
+        nonce: 0x0000000007073c07,
+        mix_hash: [0; 32],
+    }
+}
+
+#[test]
+fn test_header_serialization() {
+    let header = header();
+    let bytes = header.to_bytes();
+    assert_eq!(bytes.len(), HEADER_SIZE);
+    assert_eq!(&bytes[..4], &[0x00, 0x00, 0x00, 0x20]);
+    assert_eq!(&bytes[76..80], &[49, 0, 0, 0]);
+    assert_eq!(&bytes[80..88], &[0x07, 0x3c, 0x07, 0x07, 0, 0, 0, 0]);
+    assert_eq!(BlockHeader::from_bytes(&bytes).unwrap(), header);
+    assert!(BlockHeader::from_bytes(&bytes[..80]).is_err());
+
+    // nonce and mix hash are not part of the header hash, the digest is the reversed
+    // SHA256d of the first 80 bytes as computed by Python's hashlib
+    assert_eq!(
+        hex::encode(header.header_hash()),
+        "04e8e0d808f0a6c9ae22ffd7d9a33ec3e36cb6541133973bfff5a7544ffbee43"
+    );
+    let other = BlockHeader {
+        nonce: 1,
+        mix_hash: [3; 32],
+        ..header.clone()
+    };
+    assert_eq!(other.header_hash(), header.header_hash());
+    let other = BlockHeader {
+        time: header.time + 1,
+        ..header.clone()
+    };
+    assert_ne!(other.header_hash(), header.header_hash());
+}
+
+#[test]
+fn test_header_target() {
+    let target = |bits| BlockHeader { bits, ..header() }.target().map(hex::encode);
+    assert_eq!(
+        target(0x1d00ffff).unwrap(),
+        "00000000ffff0000000000000000000000000000000000000000000000000000"
+    );
+    assert_eq!(
+        target(0x207fffff).unwrap(),
+        "7fffff0000000000000000000000000000000000000000000000000000000000"
+    );
+    assert_eq!(
+        target(0x03000001).unwrap(),
+        "0000000000000000000000000000000000000000000000000000000000000001"
+    );
+    assert_eq!(target(0x01003456).unwrap(), hex::encode([0u8; 32]));
+    assert_eq!(target(0x04923456), None);
+    assert_eq!(target(0x2101ffff), None);
+}
+
+#[test]
+fn test_verify_block_header() {
+    // TODO: pin a mined Ravencoin header with its block hash, header hash and mix hash,
+    // the mix of this synthetic header is computed by the client under test
+    let client = Client::new_raven_coin(PathBuf::from("/tmp"));
+    let mut header = header();
+    let (mix, _) = client
+        .compute(&header.header_hash(), header.height as u64, header.nonce)
+        .unwrap();
+    header.mix_hash.copy_from_slice(&mix);
+    header.mix_hash.reverse();
+    assert_eq!(
+        client.verify_block_header(&header).unwrap(),
+        Verification::Valid
+    );
+
+    let mut bad_mix = header.clone();
+    bad_mix.mix_hash[0] ^= 1;
+    assert_eq!(
+        client.verify_block_header(&bad_mix).unwrap(),
+        Verification::MixMismatch
+    );
+
+    // the bits are part of the header hash, so the mix has to be recomputed
+    let mut hard = BlockHeader {
+        bits: 0x03000001,
+        ..header.clone()
+    };
+    let (mix, _) = client
+        .compute(&hard.header_hash(), hard.height as u64, hard.nonce)
+        .unwrap();
+    hard.mix_hash.copy_from_slice(&mix);
+    hard.mix_hash.reverse();
+    assert_eq!(
+        client.verify_block_header(&hard).unwrap(),
+        Verification::AboveTarget
+    );
+
+    let negative = BlockHeader {
+        bits: 0x04923456,
+        ..header
+    };
+    assert!(client.verify_block_header(&negative).is_err());
+}
@@ -0,0 +1,63 @@
+use powkit::algorithm::{new_algorithm, ALGORITHMS};
+use std::path::PathBuf;
+
+#[test]
+fn test_new_algorithm() {
+    for name in ALGORITHMS {
+        assert!(
+            new_algorithm(name, PathBuf::from("/tmp")).is_ok(),
+            "{}",
+            name
+        );
+    }
+    assert!(new_algorithm("KawPow", PathBuf::from("/tmp")).is_ok());
+    assert!(new_algorithm("sha256d", PathBuf::from("/tmp")).is_err());
+}
+
+#[test]
+fn test_algorithm_epoch() {
+    let ethash = new_algorithm("ethash", PathBuf::from("/tmp")).unwrap();
+    let etchash = new_algorithm("etchash", PathBuf::from("/tmp")).unwrap();
+    assert_eq!(ethash.epoch(11_700_000), 390);
+    assert_eq!(etchash.epoch(11_699_999), 389);
+    assert_eq!(etchash.epoch(11_700_000), 195);
+    assert_eq!(
+        etchash.seed_hash(11_700_000).unwrap(),
+        ethash.seed_hash(11_700_000).unwrap()
+    );
+    assert_eq!(
+        etchash.cache_size(11_700_000).unwrap(),
+        ethash.dag_config(0).cache_size(195)
+    );
+    assert_eq!(
+        etchash.dataset_size(11_700_000).unwrap(),
+        ethash.dag_config(0).dataset_size(195)
+    );
+
+    let kawpow = new_algorithm("kawpow", PathBuf::from("/tmp")).unwrap();
+    assert_eq!(kawpow.epoch(7499), 0);
+    assert_eq!(kawpow.epoch(7500), 1);
+    assert_eq!(kawpow.seed_hash(0).unwrap(), [0; 32]);
+}
+
+#[test]
+fn test_algorithm_verify() {
+    let kawpow = new_algorithm("kawpow", PathBuf::from("/tmp")).unwrap();
+    let hash: [u8; 32] =
+        hex::decode("63155f732f2bf556967f906155b510c917e48e99685ead76ea83f4eca03ab12b")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let mix =
+        hex::decode("d36f7e815ee09e74eceb9c96993a3d681edf2bf0921fc7bb710364042db99777").unwrap();
+    let target: [u8; 32] =
+        hex::decode("e7ced124598fd2500a55ad9f9f48e3569327fe50493c77a4ac9799b96efb9463")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x0000000007073c07;
+    let result = kawpow.verify(&hash, 49, nonce, &mix, &target).unwrap();
+    assert!(result.is_valid());
+    let result = kawpow.verify(&hash, 49, nonce, &[0; 32], &target).unwrap();
+    assert!(!result.is_valid());
+}
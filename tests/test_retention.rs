@@ -0,0 +1,121 @@
+use fs2::FileExt;
+use powkit::internal::dag::config::{DagConfig, DataKind};
+use powkit::internal::dag::Dag;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::tiny_config;
+
+/// Two epochs in memory and on disk.
+fn retention_config(name: &str) -> DagConfig {
+    DagConfig {
+        caches_count: 2,
+        caches_on_disk: 2,
+        ..tiny_config(name)
+    }
+}
+
+#[test]
+fn test_memory_retention() {
+    let dag = Dag::new(retention_config("RVNRETAINMEM"));
+    let first = dag.get_cache(0).unwrap();
+    dag.get_cache(1).unwrap();
+    dag.get_cache(0).unwrap();
+    assert_eq!(dag.cached_epochs(), vec![1, 0]);
+    dag.get_cache(2).unwrap();
+    assert_eq!(dag.cached_epochs(), vec![0, 2]);
+    dag.get_cache(3).unwrap();
+    assert_eq!(dag.cached_epochs(), vec![2, 3]);
+
+    // an evicted cache stays mapped while a clone is alive
+    assert_eq!(first.epoch(), 0);
+    assert_eq!(first.cache().len(), dag.config.cache_size(0));
+}
+
+#[test]
+fn test_disk_retention() {
+    let cfg = retention_config("RVNRETAINDISK");
+    for epoch in 0..4 {
+        let _ = std::fs::remove_file(cfg.file_path(epoch, DataKind::Dataset));
+    }
+    let exists = |epoch| cfg.file_path(epoch, DataKind::Dataset).exists();
+    // last used `secs` ago, the modification times of files generated in a row may be equal
+    let set_used = |epoch, secs| {
+        File::options()
+            .write(true)
+            .open(cfg.file_path(epoch, DataKind::Dataset))
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(secs))
+            .unwrap();
+    };
+    let dag = Dag::new(cfg.clone());
+    dag.get_dataset(0).unwrap();
+    dag.get_dataset(1).unwrap();
+    set_used(0, 7200);
+    set_used(1, 3600);
+    // using epoch 0 again marks it as the most recently used
+    dag.get_dataset(0).unwrap();
+    dag.get_dataset(2).unwrap();
+    assert!(exists(0));
+    assert!(!exists(1));
+    assert!(exists(2));
+
+    set_used(0, 3600);
+    dag.get_dataset(3).unwrap();
+    assert!(!exists(0));
+    assert!(exists(2));
+    assert!(exists(3));
+}
+
+#[test]
+fn test_disk_retention_removes_orphans() {
+    let cfg = retention_config("RVNRETAINORPHAN");
+    let path = |epoch, suffix: &str| {
+        PathBuf::from(format!(
+            "{}{}",
+            cfg.file_path(epoch, DataKind::Dataset).display(),
+            suffix
+        ))
+    };
+    for epoch in 0..2 {
+        let _ = fs::remove_file(cfg.file_path(epoch, DataKind::Dataset));
+    }
+    // interrupted generations of epochs without a data file, the one of epoch 8 is
+    // still going on in another process
+    for suffix in [".tmp", ".progress"] {
+        fs::write(path(7, suffix), [0]).unwrap();
+        fs::write(path(8, suffix), [0]).unwrap();
+    }
+    let lock = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(cfg.lock_path(8, DataKind::Dataset))
+        .unwrap();
+    lock.lock_exclusive().unwrap();
+
+    Dag::new(cfg.clone()).get_dataset(0).unwrap();
+    for suffix in [".tmp", ".progress"] {
+        assert!(!path(7, suffix).exists());
+        assert!(path(8, suffix).exists());
+    }
+    assert!(cfg.lock_path(8, DataKind::Dataset).exists());
+
+    lock.unlock().unwrap();
+    Dag::new(cfg.clone()).get_dataset(1).unwrap();
+    assert!(!path(8, ".tmp").exists());
+    assert!(!path(8, ".progress").exists());
+}
+
+#[test]
+fn test_zero_retention_is_invalid() {
+    let dag = Dag::new(DagConfig {
+        caches_count: 0,
+        ..tiny_config("RVNRETAINZERO")
+    });
+    assert!(dag.get_cache(0).is_err());
+}
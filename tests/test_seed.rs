@@ -0,0 +1,75 @@
+use powkit::algorithm::PowAlgorithm;
+use powkit::error::Error;
+use powkit::etchash;
+use powkit::internal::dag::config::{MAX_EPOCH, MAX_SEED_EPOCH};
+use std::path::PathBuf;
+
+#[test]
+fn test_seed_table() {
+    let eth = powkit::ethash::client::Client::new_ethereum(PathBuf::from("/tmp"));
+    let cfg = eth.config();
+    assert_eq!(cfg.seed_for_epoch(0).unwrap(), [0; 32]);
+    assert_eq!(
+        hex::encode(cfg.seed_for_epoch(1).unwrap()),
+        "290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"
+    );
+    for epoch in [0, 1, 2, 390, 1000] {
+        let seed = cfg.seed_for_epoch(epoch).unwrap();
+        assert_eq!(seed, cfg.seed_hash(epoch * cfg.epoch_length + 1).unwrap());
+        assert_eq!(cfg.epoch_for_seed(&seed), Some(epoch));
+    }
+    assert_eq!(cfg.epoch_for_seed(&[1; 32]), None);
+    assert_eq!(
+        cfg.epoch_for_seed(&cfg.seed_for_epoch(MAX_SEED_EPOCH).unwrap()),
+        Some(MAX_SEED_EPOCH)
+    );
+
+    // ECIP-1099 epochs use every other seed of the chain
+    let etc = etchash::client::Client::new_ethereum_classic(PathBuf::from("/tmp"));
+    let etc_cfg = etc.dag_config(etchash::client::ECIP1099_MAINNET_HEIGHT);
+    assert_eq!(
+        etc_cfg.seed_for_epoch(195).unwrap(),
+        cfg.seed_for_epoch(390).unwrap()
+    );
+    assert_eq!(
+        etc_cfg.epoch_for_seed(&cfg.seed_for_epoch(390).unwrap()),
+        Some(195)
+    );
+    assert_eq!(
+        etc_cfg.epoch_for_seed(&cfg.seed_for_epoch(391).unwrap()),
+        None
+    );
+
+    let rvn = powkit::kawpow::client::Client::new_raven_coin(PathBuf::from("/tmp"));
+    assert_eq!(
+        rvn.config()
+            .epoch_for_seed(&cfg.seed_for_epoch(520).unwrap()),
+        Some(520)
+    );
+}
+
+#[test]
+fn test_seed_out_of_range() {
+    let eth = powkit::ethash::client::Client::new_ethereum(PathBuf::from("/tmp"));
+    let cfg = eth.config();
+    assert!(matches!(
+        cfg.seed_for_epoch(MAX_EPOCH + 1),
+        Err(Error::EpochOutOfRange(epoch)) if epoch == MAX_EPOCH + 1
+    ));
+    assert!(matches!(
+        cfg.seed_for_epoch(usize::MAX),
+        Err(Error::EpochOutOfRange(_))
+    ));
+    assert!(matches!(
+        cfg.seed_hash(usize::MAX),
+        Err(Error::EpochOutOfRange(_))
+    ));
+
+    let mut huge = cfg.clone();
+    huge.epoch_length = usize::MAX / 2;
+    assert!(matches!(huge.validate(), Err(Error::InvalidConfig(_))));
+    assert!(matches!(
+        huge.seed_for_epoch(4),
+        Err(Error::EpochOutOfRange(4))
+    ));
+}
@@ -0,0 +1,133 @@
+use powkit::algorithm::Verification;
+use powkit::kawpow::client::{Client, Share};
+use std::path::PathBuf;
+
+#[test]
+fn test_verify_raven_coin() {
+    let client = Client::new_raven_coin(PathBuf::from("/tmp"));
+    let hash: [u8; 32] =
+        hex::decode("63155f732f2bf556967f906155b510c917e48e99685ead76ea83f4eca03ab12b")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x0000000007073c07;
+    let mix =
+        hex::decode("d36f7e815ee09e74eceb9c96993a3d681edf2bf0921fc7bb710364042db99777").unwrap();
+    // the final hash itself is the lowest valid boundary
+    let boundary: [u8; 32] =
+        hex::decode("e7ced124598fd2500a55ad9f9f48e3569327fe50493c77a4ac9799b96efb9463")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let verify =
+        |mix: &[u8], target: &[u8; 32]| client.verify(&hash, 49, nonce, mix, target).unwrap();
+
+    assert_eq!(verify(&mix, &boundary), Verification::Valid);
+    assert_eq!(verify(&mix, &[0xff; 32]), Verification::Valid);
+
+    let mut below = boundary;
+    below[31] -= 1;
+    assert_eq!(verify(&mix, &below), Verification::AboveTarget);
+
+    let mut bad_mix = mix.clone();
+    bad_mix[0] ^= 1;
+    assert_eq!(verify(&bad_mix, &[0xff; 32]), Verification::MixMismatch);
+    assert_eq!(verify(&mix[..31], &[0xff; 32]), Verification::MixMismatch);
+}
+
+#[test]
+fn test_precheck_raven_coin() {
+    // the DAG cannot be created in a missing directory, so only the pre-check can succeed
+    let client = Client::new_raven_coin(PathBuf::from("/nonexistent/powkit"));
+    let hash: [u8; 32] =
+        hex::decode("63155f732f2bf556967f906155b510c917e48e99685ead76ea83f4eca03ab12b")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let nonce = 0x0000000007073c07;
+    let mix =
+        hex::decode("d36f7e815ee09e74eceb9c96993a3d681edf2bf0921fc7bb710364042db99777").unwrap();
+    let boundary: [u8; 32] =
+        hex::decode("e7ced124598fd2500a55ad9f9f48e3569327fe50493c77a4ac9799b96efb9463")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let mut below = boundary;
+    below[31] -= 1;
+
+    assert!(client.precheck(&hash, nonce, &mix, &boundary));
+    assert!(!client.precheck(&hash, nonce, &mix, &below));
+    assert!(!client.precheck(&hash, nonce, &mix[..31], &[0xff; 32]));
+
+    assert_eq!(
+        client.verify(&hash, 49, nonce, &mix, &below).unwrap(),
+        Verification::AboveTarget
+    );
+    assert!(client.verify(&hash, 49, nonce, &mix, &boundary).is_err());
+}
+
+#[test]
+fn test_verify_batch_raven_coin() {
+    let client = Client::new_raven_coin(PathBuf::from("/tmp"));
+    let share = |height, nonce, hash: &str, mix: &str, digest: &str| Share {
+        header_hash: hex::decode(hash).unwrap().try_into().unwrap(),
+        height,
+        nonce,
+        mix: hex::decode(mix).unwrap(),
+        target: hex::decode(digest).unwrap().try_into().unwrap(),
+    };
+    let mut shares = vec![
+        share(
+            29950,
+            0x005d409dbc23a62a,
+            "ac7b55e801511b77e11d52e9599206101550144525b5679f2dab19386f23dcce",
+            "5359807b77a74878269c3a3044df8618a576ce8dc52e1c48d927d4a60e7c6b79",
+            "022019e5408683f7f8326b4e46b42864a3a069f17b6151e434fcaedecaadd918",
+        ),
+        share(
+            49,
+            0x0000000007073c07,
+            "63155f732f2bf556967f906155b510c917e48e99685ead76ea83f4eca03ab12b",
+            "d36f7e815ee09e74eceb9c96993a3d681edf2bf0921fc7bb710364042db99777",
+            "e7ced124598fd2500a55ad9f9f48e3569327fe50493c77a4ac9799b96efb9463",
+        ),
+        share(
+            30000,
+            0x005db8607994ff30,
+            "d34519f72c97cae8892c277776259db3320820cb5279a299d0ef1e155e5c6454",
+            "de0348b69bf91dfe2c3d3dba6f0132e9048a5284e57b8d9d20adc5f3dc0d3236",
+            "c7953d848cda6e304f77b4c6d735645c8e8508a5e74c9e9814ef37b19087cd6c",
+        ),
+        share(
+            50,
+            0x00000000076e482e,
+            "9e7248f20914913a73d80a70174c331b1d34f260535ac3631d770e656b5dd922",
+            "d6dc634ae837e2785b347648ea515e25e5d8821ae0b95e1c2a9c2d497e0dcfbd",
+            "ab0ad7ef8d8ee317dd12d10310aceed7321d34fb263791c2de5776a6658d177e",
+        ),
+    ];
+    // the share of height 49 at height 99 is in the same epoch but has another mix
+    let mut wrong_height = shares[1].clone();
+    wrong_height.height = 99;
+    shares.push(wrong_height);
+    let mut above_target = shares[3].clone();
+    above_target.target[0] = 0;
+    shares.push(above_target);
+
+    let expected = vec![
+        Verification::Valid,
+        Verification::Valid,
+        Verification::Valid,
+        Verification::Valid,
+        Verification::MixMismatch,
+        Verification::AboveTarget,
+    ];
+    for workers in [1, 3, 16] {
+        let batch = client.verify_batch(&shares, workers);
+        let results: Vec<_> = batch.results.iter().map(|r| *r.as_ref().unwrap()).collect();
+        assert_eq!(results, expected);
+        assert_eq!(batch.epochs, 3);
+        assert_eq!(batch.valid_count(), 4);
+    }
+    assert!(client.verify_batch(&[], 4).results.is_empty());
+}